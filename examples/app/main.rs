@@ -1,13 +1,18 @@
 //! The example demonstrates the advanced usage of the framework covering all available features.
 //!
 //! For detailed information about each feature, refer to the documentation for each respective module.
-use std::{env, time::Duration};
+use std::{env, sync::Arc, time::Duration};
 
 use dotenvy::dotenv;
 use seance::{SessionCollector, SessionManager, backend::fs::FilesystemBackend};
 use tempfile::tempdir;
 
-use carapax::{App, Chain, Context, ErrorExt, HandlerError, api::Client, handler::LongPoll};
+use carapax::{
+    App, Chain, Context, ErrorExt, HandlerError,
+    api::{Client, ExecuteError},
+    handler::{LongPoll, LongPollOptions},
+    types::GetBot,
+};
 
 mod access;
 mod command;
@@ -28,8 +33,7 @@ async fn main() {
     context.insert(client.clone());
 
     let session_backend = create_session_backend();
-    spawn_session_collector(session_backend.clone());
-    let session_manager = SessionManager::new(session_backend);
+    let session_manager = SessionManager::new(session_backend.clone());
     context.insert(session_manager);
 
     // Chain is a handler which allows to execute several handlers, one after another.
@@ -53,8 +57,14 @@ async fn main() {
     // for errors returned by the handler.
     let handler = chain.on_error(error_handler);
 
-    let app = App::new(context, handler);
-    LongPoll::new(client, app).run().await
+    // Startup hooks allow to prepare the app before it starts to process updates.
+    let app = App::new(context, handler)
+        .on_startup(log_bot_info)
+        .on_startup(move |_| spawn_session_collector(session_backend.clone()));
+    app.run_startup_hooks().await.expect("Failed to start the app");
+    // Receive only kinds of updates the handlers are able to process.
+    let options = app.configure_long_poll(LongPollOptions::default());
+    LongPoll::new(client, app).with_options(options).run().await
 }
 
 fn get_env(s: &str) -> String {
@@ -67,8 +77,15 @@ fn create_session_backend() -> FilesystemBackend {
     FilesystemBackend::new(tmpdir.path())
 }
 
+async fn log_bot_info(context: Arc<Context>) -> Result<(), ExecuteError> {
+    let client = context.get::<Client>().expect("Client is not registered");
+    let bot = client.execute(GetBot).await?;
+    log::info!("Running as @{}", bot.username);
+    Ok(())
+}
+
 /// Spawns a garbage collector for expired sessions
-fn spawn_session_collector(backend: FilesystemBackend) {
+async fn spawn_session_collector(backend: FilesystemBackend) {
     let gc_period = get_env("CARAPAX_SESSION_GC_PERIOD");
     let gc_period = Duration::from_secs(
         gc_period
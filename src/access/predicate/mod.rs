@@ -1,8 +1,8 @@
-use std::fmt;
+use std::{any::type_name, fmt};
 
 use crate::{
     access::policy::AccessPolicy,
-    core::{Handler, HandlerInput},
+    core::{Handler, HandlerDescription, HandlerInput},
     types::{ChatPeerId, ChatUsername, Update, UserPeerId, UserUsername},
 };
 
@@ -37,6 +37,10 @@ where
         let value = self.policy.is_granted(input).await;
         match value {
             Ok(value) => {
+                #[cfg(feature = "metrics")]
+                if !value {
+                    crate::metrics::registry().record_access_denial();
+                }
                 log::info!(
                     "Access for {:?} is {}",
                     debug_principal,
@@ -47,6 +51,10 @@ where
             Err(err) => Err(err),
         }
     }
+
+    fn describe(&self) -> HandlerDescription {
+        HandlerDescription::new(format!("access {}", type_name::<P>()))
+    }
 }
 
 struct DebugPrincipal {
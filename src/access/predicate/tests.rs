@@ -93,3 +93,12 @@ async fn access_predicate() {
     let result = predicate.handle(input_error).await;
     assert!(result.is_err());
 }
+
+#[test]
+fn describe_access_predicate() {
+    let predicate = AccessPredicate::new(PolicyMock);
+    assert_eq!(
+        predicate.describe().get_name(),
+        format!("access {}::PolicyMock", module_path!())
+    );
+}
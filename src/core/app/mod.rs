@@ -1,13 +1,24 @@
-use std::{marker::PhantomData, sync::Arc};
+use std::{collections::HashSet, marker::PhantomData, sync::Arc};
+
+use futures_util::future::Abortable;
 
 use crate::{
     core::{
+        cache::with_update_cache,
         context::Context,
         convert::TryFromInput,
-        handler::{Handler, HandlerInput, IntoHandlerResult},
+        dedup::{BoxedDedupStore, DedupStore, is_dedup_skipped},
+        describe::HandlerDescription,
+        dispatch::{Dispatch, DispatchKey, OrderedDispatch},
+        handler::{Handler, HandlerError, HandlerInput, HandlerResult, IntoHandlerResult},
+        hook::{Hook, Hooks},
+        limit::ConcurrencyLimit,
+        middleware::{Middleware, MiddlewareStack},
+        shutdown::{InFlight, ShutdownHandle},
+        sink::{AppError, BoxedErrorSink, ErrorSink},
     },
-    handler::UpdateHandler,
-    types::Update,
+    handler::{LongPollOptions, UpdateHandler},
+    types::{AllowedUpdate, SetWebhook, Update},
 };
 
 #[cfg(test)]
@@ -21,11 +32,37 @@ mod tests;
 /// Wraps an update into the [`HandlerInput`] struct and passes it to the inner handler.
 ///
 /// Use [`crate::Chain`] struct to configure multiple handlers.
-#[derive(Clone)]
 pub struct App<H, HI> {
     context: Arc<Context>,
     handler: H,
     handler_input: PhantomData<HI>,
+    dedup_store: Option<Arc<dyn BoxedDedupStore>>,
+    dispatch: Option<Arc<dyn Dispatch>>,
+    concurrency_limit: Option<ConcurrencyLimit>,
+    in_flight: Arc<InFlight>,
+    hooks: Arc<Hooks>,
+    middlewares: MiddlewareStack,
+    error_sink: Option<Arc<dyn BoxedErrorSink>>,
+}
+
+impl<H, HI> Clone for App<H, HI>
+where
+    H: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            context: self.context.clone(),
+            handler: self.handler.clone(),
+            handler_input: PhantomData,
+            dedup_store: self.dedup_store.clone(),
+            dispatch: self.dispatch.clone(),
+            concurrency_limit: self.concurrency_limit.clone(),
+            in_flight: self.in_flight.clone(),
+            hooks: self.hooks.clone(),
+            middlewares: self.middlewares.clone(),
+            error_sink: self.error_sink.clone(),
+        }
+    }
 }
 
 impl<H, HI, HO> App<H, HI>
@@ -46,30 +83,303 @@ where
             context: Arc::new(context),
             handler,
             handler_input: PhantomData,
+            dedup_store: None,
+            dispatch: None,
+            concurrency_limit: None,
+            in_flight: Default::default(),
+            hooks: Default::default(),
+            middlewares: Default::default(),
+            error_sink: None,
         }
     }
 
+    /// Adds a hook to run when the app starts.
+    ///
+    /// Hooks run in the order they are added by [`Self::run_startup_hooks`].
+    ///
+    /// # Arguments
+    ///
+    /// * `hook` - The hook to add.
+    ///
+    /// # Panics
+    ///
+    /// Panics when trying to add a hook to a shared app.
+    pub fn on_startup<T: Hook>(mut self, hook: T) -> Self {
+        self.get_hooks_mut().add_startup(hook);
+        self
+    }
+
+    /// Adds a hook to run when the app stops.
+    ///
+    /// Hooks run in the order they are added by [`ShutdownHandle::shutdown`],
+    /// after in-flight updates are processed.
+    ///
+    /// # Arguments
+    ///
+    /// * `hook` - The hook to add.
+    ///
+    /// # Panics
+    ///
+    /// Panics when trying to add a hook to a shared app.
+    pub fn on_shutdown<T: Hook>(mut self, hook: T) -> Self {
+        self.get_hooks_mut().add_shutdown(hook);
+        self
+    }
+
+    fn get_hooks_mut(&mut self) -> &mut Hooks {
+        Arc::get_mut(&mut self.hooks).expect("Can not add hook, app is shared")
+    }
+
+    /// Runs startup hooks.
+    ///
+    /// Call this method before passing the app to [`crate::handler::LongPoll`]
+    /// or [`crate::handler::WebhookServer`].
+    ///
+    /// Returns an error of the first failed hook; subsequent hooks will not run.
+    pub async fn run_startup_hooks(&self) -> HandlerResult {
+        self.hooks.run_startup(self.context.clone()).await
+    }
+
+    /// Returns a handle allowing to stop the app gracefully.
+    ///
+    /// See [`ShutdownHandle::shutdown`] for more information.
+    #[must_use]
+    pub fn get_shutdown_handle(&self) -> ShutdownHandle {
+        ShutdownHandle::new(self.in_flight.clone(), self.context.clone(), self.hooks.clone())
+    }
+
+    /// Enables deduplication of updates.
+    ///
+    /// An update with an ID found in the store is discarded.
+    /// This is useful when an update is delivered more than once,
+    /// e.g. when a webhook request is retried or the polling loop is restarted.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - A store of seen update IDs, e.g. [`crate::MemoryDedupStore`].
+    pub fn with_dedup_store<S>(mut self, store: S) -> Self
+    where
+        S: DedupStore,
+    {
+        self.dedup_store = Some(Arc::new(store));
+        self
+    }
+
+    /// Enables ordered processing of updates.
+    ///
+    /// By default, all updates are processed independently,
+    /// so two updates from the same chat can be handled out of order.
+    ///
+    /// # Arguments
+    ///
+    /// * `dispatch` - Defines how to group updates, see [`OrderedDispatch`] for more information.
+    pub fn with_ordered_dispatch<K>(mut self, dispatch: OrderedDispatch<K>) -> Self
+    where
+        K: DispatchKey,
+    {
+        self.dispatch = Some(Arc::new(dispatch));
+        self
+    }
+
+    /// Limits the number of updates processed at the same time.
+    ///
+    /// There is no limit by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - The limit, see [`ConcurrencyLimit`] for available policies.
+    pub fn with_concurrency_limit(mut self, limit: ConcurrencyLimit) -> Self {
+        self.concurrency_limit = Some(limit);
+        self
+    }
+
+    /// Returns kinds of updates the handler is able to process.
+    ///
+    /// See [`Handler::allowed_updates`] for more information.
+    pub fn get_allowed_updates(&self) -> HashSet<AllowedUpdate> {
+        self.handler.allowed_updates()
+    }
+
+    /// Returns a description of the handler.
+    ///
+    /// See [`Handler::describe`] for more information.
+    pub fn describe(&self) -> HandlerDescription {
+        self.handler.describe()
+    }
+
+    /// Adds kinds of updates the handler is able to process to long polling options.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - Options to configure.
+    pub fn configure_long_poll(&self, options: LongPollOptions) -> LongPollOptions {
+        self.get_allowed_updates()
+            .into_iter()
+            .fold(options, |options, kind| options.with_allowed_update(kind))
+    }
+
+    /// Sets kinds of updates the handler is able to process to a webhook method.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - A method to configure.
+    pub fn configure_webhook(&self, method: SetWebhook) -> SetWebhook {
+        method.with_allowed_updates(self.get_allowed_updates())
+    }
+
+    /// Sets a sink for errors.
+    ///
+    /// By default, errors are logged.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` - The sink to receive errors of the input conversion and the handler.
+    pub fn with_error_sink<S>(mut self, sink: S) -> Self
+    where
+        S: ErrorSink,
+    {
+        self.error_sink = Some(Arc::new(sink));
+        self
+    }
+
+    /// Adds a middleware to run around the handler.
+    ///
+    /// Middlewares run in the order they are added.
+    ///
+    /// # Arguments
+    ///
+    /// * `middleware` - The middleware to add.
+    ///
+    /// # Panics
+    ///
+    /// Panics when trying to add a middleware to a shared app.
+    pub fn with_middleware<M>(mut self, middleware: M) -> Self
+    where
+        M: Middleware,
+    {
+        self.middlewares.push(middleware);
+        self
+    }
+}
+
+impl<H, HI, HO> App<H, HI>
+where
+    H: Handler<HI, Output = HO> + Sync + 'static,
+    HI: TryFromInput + Sync + 'static,
+    HI::Error: 'static,
+    HO: IntoHandlerResult + Send + 'static,
+{
     async fn handle_update(&self, update: Update) {
+        let update_id = update.id;
+        let (mut guard, registration) = match self.in_flight.register() {
+            Some(registration) => registration,
+            None => {
+                log::warn!("Update {update_id} discarded: app is shutting down");
+                return;
+            }
+        };
+        #[cfg(feature = "metrics")]
+        crate::metrics::registry().record_update(crate::core::kind::UpdateKindExt::get_kind_name(&update));
+        #[cfg(feature = "tracing")]
+        let span = create_update_span(&update);
+        let future = Abortable::new(with_update_cache(self.process_update(update)), registration);
+        #[cfg(feature = "tracing")]
+        let future = tracing::Instrument::instrument(future, span);
+        if future.await.is_err() {
+            guard.cancel();
+            log::warn!("Update {update_id} has been cancelled: app is shutting down");
+        }
+    }
+
+    async fn process_update(&self, update: Update) {
         let input = HandlerInput {
             update,
             context: self.context.clone(),
+            extensions: Default::default(),
         };
-        let handler = self.handler.clone();
-        let input = match HI::try_from_input(input).await {
-            Ok(Some(input)) => input,
-            Ok(None) => return,
-            Err(err) => {
-                log::error!("Failed to convert input: {err}");
-                return;
+        // Take a place in the queue before any other await, so that the order of updates is preserved
+        let _permit = match self.dispatch {
+            Some(ref dispatch) => match dispatch.acquire(input.clone()).await {
+                Some(permit) => Some(permit),
+                None => {
+                    log::warn!("Update {} discarded: dispatch queue is full", input.update.id);
+                    return;
+                }
+            },
+            None => None,
+        };
+        if let Some(ref store) = self.dedup_store
+            && !is_dedup_skipped()
+        {
+            match store.insert_boxed(input.update.id).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    log::info!("Update {} discarded: already seen", input.update.id);
+                    return;
+                }
+                Err(err) => log::error!("Failed to check whether update {} is seen: {err}", input.update.id),
             }
+        }
+        match self.concurrency_limit {
+            Some(ref limit) => match limit.acquire().await {
+                Some(mut permit) => {
+                    let update_id = input.update.id;
+                    let future = Abortable::new(self.run_handler(input), permit.take_registration());
+                    if future.await.is_err() {
+                        log::warn!("Update {update_id} has been cancelled in favor of a newer one");
+                    }
+                }
+                None => log::warn!("Update {} discarded: concurrency limit is reached", input.update.id),
+            },
+            None => self.run_handler(input).await,
+        }
+    }
+
+    async fn run_handler(&self, input: HandlerInput) {
+        let handler = self.handler.clone();
+        let update = self.error_sink.as_ref().map(|_| input.update.clone());
+        let result = if self.middlewares.is_empty() {
+            call_handler(handler, input).await
+        } else {
+            self.middlewares
+                .run(input, move |input| Box::pin(call_handler(handler, input)))
+                .await
         };
-        let future = handler.handle(input);
-        if let Err(err) = future.await.into_result() {
-            log::error!("An error has occurred: {err}");
+        if let Err(err) = result.map_err(AppError::new) {
+            match (&self.error_sink, update) {
+                (Some(sink), Some(update)) => sink.handle_boxed(err, update, self.context.clone()).await,
+                _ => log::error!("{err}"),
+            }
         }
     }
 }
 
+async fn call_handler<H, HI>(handler: H, input: HandlerInput) -> HandlerResult
+where
+    H: Handler<HI>,
+    H::Output: IntoHandlerResult,
+    HI: TryFromInput,
+    HI::Error: 'static,
+{
+    match HI::try_from_input(input).await {
+        Ok(Some(input)) => handler.handle(input).await.into_result(),
+        Ok(None) => Ok(()),
+        Err(err) => Err(HandlerError::convert(err)),
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn create_update_span(update: &Update) -> tracing::Span {
+    use crate::core::kind::UpdateKindExt;
+    tracing::info_span!(
+        "update",
+        update_id = update.id,
+        chat_id = update.get_chat_id().map(i64::from),
+        user_id = update.get_user_id().map(i64::from),
+        kind = update.get_kind_name(),
+    )
+}
+
 impl<H, HI, HO> UpdateHandler for App<H, HI>
 where
     H: Handler<HI, Output = HO> + Sync + 'static,
@@ -63,3 +63,146 @@ async fn handle() {
 
     assert_eq!(*counter.value.lock().await, 2);
 }
+
+#[cfg(feature = "tracing")]
+mod tracing_spans {
+    use std::sync::Mutex as StdMutex;
+
+    use tracing::{
+        Subscriber,
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+    };
+    use tracing_subscriber::{
+        Layer,
+        layer::{Context as LayerContext, SubscriberExt},
+        registry::LookupSpan,
+    };
+
+    use crate::core::predicate::PredicateExt;
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Recorder {
+        spans: Arc<StdMutex<Vec<String>>>,
+    }
+
+    struct SpanIndex(usize);
+
+    struct Fields(String);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.0.push_str(&format!(" {}={:?}", field.name(), value));
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.push_str(&format!(" {}={}", field.name(), value));
+        }
+    }
+
+    impl<S> Layer<S> for Recorder
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: LayerContext<'_, S>) {
+            let mut fields = Fields(attrs.metadata().name().to_string());
+            attrs.record(&mut fields);
+            let mut spans = self.spans.lock().unwrap();
+            ctx.span(id).unwrap().extensions_mut().insert(SpanIndex(spans.len()));
+            spans.push(fields.0);
+        }
+
+        fn on_record(&self, id: &Id, values: &Record<'_>, ctx: LayerContext<'_, S>) {
+            let mut fields = Fields(String::new());
+            values.record(&mut fields);
+            let span = ctx.span(id).unwrap();
+            let extensions = span.extensions();
+            let SpanIndex(idx) = extensions.get::<SpanIndex>().unwrap();
+            self.spans.lock().unwrap()[*idx].push_str(&fields.0);
+        }
+    }
+
+    async fn is_ok(Ref(counter): Ref<Counter>) -> bool {
+        *counter.value.lock().await == 0
+    }
+
+    #[tokio::test]
+    async fn spans() {
+        let recorder = Recorder::default();
+        let subscriber = tracing_subscriber::registry().with(recorder.clone());
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let counter = Counter {
+            value: Arc::new(Mutex::new(0)),
+        };
+        let mut context = Context::default();
+        context.insert(counter);
+        let chain = Chain::all()
+            .with(success_handler.with_predicate(is_ok))
+            .with(success_handler.with_predicate(is_ok));
+        let app = App::new(context, chain);
+        app.handle(create_update()).await;
+
+        let spans = recorder.spans.lock().unwrap().clone();
+        assert_eq!(spans.len(), 3);
+        assert_eq!(spans[0], "update update_id=1 chat_id=1 user_id=1 kind=message");
+        assert!(spans[1].starts_with("handler name=carapax::core::predicate::base::Predicate<"));
+        assert!(spans[1].ends_with("::is_ok decision=true"));
+        assert!(spans[2].ends_with("::is_ok decision=false"));
+    }
+}
+
+#[test]
+fn allowed_updates() {
+    use crate::{
+        core::predicate::PredicateExt,
+        handler::LongPollOptions,
+        types::{AllowedUpdate, CallbackQuery, ChatMemberUpdated, Message, PollAnswer},
+    };
+
+    async fn message_handler(_: Message) {}
+    async fn callback_query_handler(_: CallbackQuery) {}
+    async fn chat_member_handler(_: ChatMemberUpdated) {}
+    async fn poll_answer_predicate(_: PollAnswer) -> bool {
+        true
+    }
+
+    let chain = Chain::once()
+        .with(message_handler)
+        .with(callback_query_handler)
+        .with(chat_member_handler)
+        .with(message_handler.with_predicate(poll_answer_predicate));
+    let app = App::new(Context::default(), chain);
+    let expected = HashSet::from([
+        AllowedUpdate::BotStatus,
+        AllowedUpdate::BusinessMessage,
+        AllowedUpdate::CallbackQuery,
+        AllowedUpdate::ChannelPost,
+        AllowedUpdate::EditedBusinessMessage,
+        AllowedUpdate::EditedChannelPost,
+        AllowedUpdate::EditedMessage,
+        AllowedUpdate::Message,
+        AllowedUpdate::UserStatus,
+    ]);
+    assert_eq!(app.get_allowed_updates(), expected);
+    assert_eq!(
+        app.configure_long_poll(LongPollOptions::default()),
+        expected.iter().fold(LongPollOptions::default(), |options, kind| options
+            .with_allowed_update(*kind))
+    );
+    assert_eq!(
+        serde_json::to_value(app.configure_webhook(SetWebhook::new("https://example.com")))
+            .unwrap()
+            .get("allowed_updates")
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .len(),
+        expected.len()
+    );
+
+    let app = App::new(Context::default(), Chain::all().with(success_handler));
+    assert_eq!(app.get_allowed_updates().len(), 23);
+}
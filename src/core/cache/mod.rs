@@ -0,0 +1,117 @@
+use std::{
+    any::{Any, TypeId},
+    collections::{HashMap, HashSet},
+    future::Future,
+    ops::Deref,
+    sync::{Mutex, MutexGuard},
+};
+
+use crate::{
+    core::{convert::TryFromInput, handler::HandlerInput},
+    types::AllowedUpdate,
+};
+
+#[cfg(test)]
+mod tests;
+
+tokio::task_local! {
+    static UPDATE_CACHE: UpdateCache;
+}
+
+#[derive(Default)]
+struct UpdateCache {
+    values: Mutex<HashMap<TypeId, Box<dyn Any + Send>>>,
+}
+
+impl UpdateCache {
+    fn lock(&self) -> MutexGuard<'_, HashMap<TypeId, Box<dyn Any + Send>>> {
+        self.values.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+/// Runs a future with an empty cache of extracted inputs.
+///
+/// [`crate::App`] runs each update in its own scope,
+/// so inputs are extracted once per update and shared between handlers.
+pub(crate) async fn with_update_cache<F>(future: F) -> F::Output
+where
+    F: Future,
+{
+    UPDATE_CACHE.scope(UpdateCache::default(), future).await
+}
+
+/// Returns a cached value of the type or stores a value returned by the future.
+///
+/// Errors are not cached.
+/// The future is always awaited when running outside of [`with_update_cache`] scope.
+pub(crate) async fn get_or_extract<T, E, F>(future: F) -> Result<Option<T>, E>
+where
+    T: Clone + Send + 'static,
+    F: Future<Output = Result<Option<T>, E>>,
+{
+    let value = UPDATE_CACHE
+        .try_with(|cache| {
+            cache
+                .lock()
+                .get(&TypeId::of::<T>())
+                .and_then(|value| value.downcast_ref::<Option<T>>())
+                .cloned()
+        })
+        .ok()
+        .flatten();
+    if let Some(value) = value {
+        return Ok(value);
+    }
+    let value = future.await?;
+    let _ = UPDATE_CACHE.try_with(|cache| {
+        cache.lock().insert(TypeId::of::<T>(), Box::new(value.clone()));
+    });
+    Ok(value)
+}
+
+/// Removes all cached values of the current update.
+///
+/// Call it when a value stored outside of the update is changed, e.g. a state in a session.
+#[cfg_attr(not(feature = "dialogue"), allow(dead_code))]
+pub(crate) fn clear_update_cache() {
+    let _ = UPDATE_CACHE.try_with(|cache| cache.lock().clear());
+}
+
+/// Allows to extract an input once per update.
+///
+/// [`crate::App`] extracts [`crate::types::Message`] and [`crate::types::Command`]
+/// inputs once per update out of the box.
+/// Use `Cached<T>` to do the same for other inputs,
+/// e.g. a dialogue input or your own [`TryFromInput`] implementations.
+///
+/// The value is shared between all handlers processing the update,
+/// so make sure it does not change during processing.
+/// A state of the dialogue is an exception: the cache is cleared when the state is saved.
+/// An input changed by a [`crate::Middleware`] is extracted again, see [`crate::Next::run`].
+#[derive(Clone)]
+pub struct Cached<T: Clone>(pub T);
+
+impl<T: Clone> Deref for Cached<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> TryFromInput for Cached<T>
+where
+    T: TryFromInput + Clone + 'static,
+{
+    type Error = T::Error;
+
+    async fn try_from_input(input: HandlerInput) -> Result<Option<Self>, Self::Error> {
+        get_or_extract(T::try_from_input(input))
+            .await
+            .map(|value| value.map(Cached))
+    }
+
+    fn allowed_updates() -> HashSet<AllowedUpdate> {
+        T::allowed_updates()
+    }
+}
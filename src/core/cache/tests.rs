@@ -0,0 +1,137 @@
+use std::{
+    convert::Infallible,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use crate::{
+    core::{App, Chain, Context, HandlerResult, MiddlewareExt, Next, Ref},
+    handler::UpdateHandler,
+    types::{Command, Message, Text, Update},
+};
+
+use super::*;
+
+#[derive(Clone, Default)]
+struct Counter(Arc<AtomicUsize>);
+
+impl Counter {
+    fn increment(&self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn get(&self) -> usize {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+#[derive(Clone)]
+struct Extracted;
+
+impl TryFromInput for Extracted {
+    type Error = Infallible;
+
+    async fn try_from_input(input: HandlerInput) -> Result<Option<Self>, Self::Error> {
+        input.context.get::<Counter>().unwrap().increment();
+        Ok(Some(Extracted))
+    }
+}
+
+async fn handler_extracted(_: Cached<Extracted>) {}
+
+async fn handler_message(counter: Ref<Counter>, _: Message, _: Text) {
+    counter.increment();
+}
+
+async fn handler_command(_: Command) {}
+
+fn create_update(text: &str) -> Update {
+    serde_json::from_value(serde_json::json!({
+        "update_id": 1,
+        "message": {
+            "message_id": 1111,
+            "date": 0,
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "chat": {"id": 1, "type": "private", "first_name": "test"},
+            "text": text
+        }
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn cached() {
+    let counter = Counter::default();
+    let mut context = Context::default();
+    context.insert(counter.clone());
+    let chain = Chain::all()
+        .with(handler_extracted)
+        .with(handler_command)
+        .with(handler_message)
+        .with(handler_extracted)
+        .with(handler_message);
+    let app = App::new(context, chain);
+    app.handle(create_update("test")).await;
+    // extracted once, handled twice
+    assert_eq!(counter.get(), 3);
+    // the cache is not shared between updates
+    app.handle(create_update("test")).await;
+    assert_eq!(counter.get(), 6);
+}
+
+#[derive(Clone, Default)]
+struct Texts(Arc<Mutex<Vec<String>>>);
+
+async fn record_text(Ref(texts): Ref<Texts>, message: Message) {
+    let text = message.get_text().map(|text| text.data.clone()).unwrap_or_default();
+    texts.0.lock().unwrap().push(text);
+}
+
+async fn rewrite_text(mut input: HandlerInput, next: Next) -> HandlerResult {
+    input.update = create_update("rewritten");
+    next.run(input).await
+}
+
+#[tokio::test]
+async fn changed_input() {
+    let texts = Texts::default();
+    let mut context = Context::default();
+    context.insert(texts.clone());
+    let chain = Chain::all()
+        .with(record_text)
+        .with(Chain::all().with_middleware(rewrite_text).with(record_text))
+        .with(record_text.with_middleware(rewrite_text))
+        .with(record_text);
+    let app = App::new(context, chain);
+    app.handle(create_update("test")).await;
+    assert_eq!(*texts.0.lock().unwrap(), vec!["test", "rewritten", "rewritten", "test"]);
+}
+
+#[tokio::test]
+async fn get_or_extract_scope() {
+    let extract = |value: usize| async move { Ok::<_, Infallible>(Some(value)) };
+    // no scope, nothing is cached
+    assert_eq!(get_or_extract(extract(1)).await.unwrap(), Some(1));
+    assert_eq!(get_or_extract(extract(2)).await.unwrap(), Some(2));
+
+    with_update_cache(async {
+        assert_eq!(get_or_extract(extract(1)).await.unwrap(), Some(1));
+        assert_eq!(get_or_extract(extract(2)).await.unwrap(), Some(1));
+        // errors are not cached
+        assert!(
+            get_or_extract(async { Err::<Option<u8>, _>(std::fmt::Error) })
+                .await
+                .is_err()
+        );
+        assert_eq!(get_or_extract(extract_u8(3)).await.unwrap(), Some(3));
+        clear_update_cache();
+        assert_eq!(get_or_extract(extract(2)).await.unwrap(), Some(2));
+    })
+    .await;
+}
+
+async fn extract_u8(value: u8) -> Result<Option<u8>, Infallible> {
+    Ok(Some(value))
+}
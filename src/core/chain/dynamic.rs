@@ -0,0 +1,191 @@
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+    fmt,
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use crate::{
+    core::{
+        chain::{Chain, ChainHandler, ChainResult, ConvertHandler},
+        convert::TryFromInput,
+        describe::HandlerDescription,
+        handler::{Handler, HandlerInput},
+    },
+    types::AllowedUpdate,
+};
+
+/// A chain of handlers which can be changed at runtime.
+///
+/// Handlers can be added and removed while the chain is shared, e.g. when it is used in a running [`crate::App`].
+/// Each change replaces the list of handlers atomically:
+/// an update being processed uses the list it started with.
+///
+/// Every handler gets a [`HandlerId`] which stays the same until the handler is removed.
+/// Use [`Self::replace`] to swap the whole chain, e.g. to add non-critical handlers or groups.
+///
+/// Note that [`Handler::allowed_updates`] returns kinds for the current list of handlers,
+/// so make sure that updates required by handlers added later are allowed.
+#[derive(Clone)]
+pub struct DynamicChain {
+    state: Arc<RwLock<State>>,
+}
+
+struct State {
+    chain: Chain,
+    ids: Vec<HandlerId>,
+    next_id: u64,
+}
+
+impl DynamicChain {
+    /// Creates a new `DynamicChain`.
+    ///
+    /// # Arguments
+    ///
+    /// * `chain` - An initial chain; strategy, fallbacks and middlewares are kept as is.
+    ///
+    /// Use [`Self::get_ids`] to get IDs of handlers added to the initial chain.
+    pub fn new(chain: Chain) -> Self {
+        let ids = (0..chain.handlers.len() as u64).map(HandlerId).collect::<Vec<_>>();
+        Self {
+            state: Arc::new(RwLock::new(State {
+                next_id: ids.len() as u64,
+                chain,
+                ids,
+            })),
+        }
+    }
+
+    /// Adds a handler to the end of the chain.
+    ///
+    /// Returns an ID of the added handler.
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - The handler to add.
+    pub fn add<H, I, O>(&self, handler: H) -> HandlerId
+    where
+        H: Handler<I, Output = O> + Sync + Clone + 'static,
+        I: TryFromInput + Sync + 'static,
+        O: Into<ChainResult>,
+    {
+        self.push(ConvertHandler::shared(handler, None, true))
+    }
+
+    /// Adds a handler with a name to the end of the chain.
+    ///
+    /// See [`Chain::with_named`] for more information.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the handler.
+    /// * `handler` - The handler to add.
+    pub fn add_named<N, H, I, O>(&self, name: N, handler: H) -> HandlerId
+    where
+        N: Into<Cow<'static, str>>,
+        H: Handler<I, Output = O> + Sync + Clone + 'static,
+        I: TryFromInput + Sync + 'static,
+        O: Into<ChainResult>,
+    {
+        self.push(ConvertHandler::shared(handler, Some(name.into()), true))
+    }
+
+    /// Removes a handler from the chain.
+    ///
+    /// Returns `false` when the handler is not found.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the handler.
+    pub fn remove(&self, id: HandlerId) -> bool {
+        let mut state = self.write();
+        let idx = match state.ids.iter().position(|x| *x == id) {
+            Some(idx) => idx,
+            None => return false,
+        };
+        let mut handlers = state.chain.handlers.as_ref().clone();
+        handlers.remove(idx);
+        state.chain.handlers = Arc::new(handlers);
+        state.ids.remove(idx);
+        true
+    }
+
+    /// Replaces the whole chain including strategy, fallbacks and middlewares.
+    ///
+    /// Handlers of the new chain get new IDs, IDs of the previous handlers are no longer valid.
+    ///
+    /// Returns IDs of the new handlers in the order they are dispatched.
+    ///
+    /// # Arguments
+    ///
+    /// * `chain` - The new chain.
+    pub fn replace(&self, chain: Chain) -> Vec<HandlerId> {
+        let mut state = self.write();
+        let start = state.next_id;
+        state.next_id += chain.handlers.len() as u64;
+        state.ids = (start..state.next_id).map(HandlerId).collect();
+        state.chain = chain;
+        state.ids.clone()
+    }
+
+    /// Returns IDs of handlers in the order they are dispatched.
+    pub fn get_ids(&self) -> Vec<HandlerId> {
+        self.read().ids.clone()
+    }
+
+    fn push(&self, handler: Arc<dyn ChainHandler + Sync>) -> HandlerId {
+        let mut state = self.write();
+        let id = HandlerId(state.next_id);
+        state.next_id += 1;
+        let mut handlers = state.chain.handlers.as_ref().clone();
+        handlers.push(handler);
+        state.chain.handlers = Arc::new(handlers);
+        state.ids.push(id);
+        id
+    }
+
+    fn get_chain(&self) -> Chain {
+        self.read().chain.clone()
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, State> {
+        self.state.read().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, State> {
+        self.state.write().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl From<Chain> for DynamicChain {
+    fn from(chain: Chain) -> Self {
+        Self::new(chain)
+    }
+}
+
+impl Handler<HandlerInput> for DynamicChain {
+    type Output = ChainResult;
+
+    async fn handle(&self, input: HandlerInput) -> Self::Output {
+        let chain = self.get_chain();
+        chain.handle(input).await
+    }
+
+    fn allowed_updates(&self) -> HashSet<AllowedUpdate> {
+        Handler::allowed_updates(&self.get_chain())
+    }
+
+    fn describe(&self) -> HandlerDescription {
+        Handler::describe(&self.get_chain())
+    }
+}
+
+/// An ID of a handler in the [`DynamicChain`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct HandlerId(u64);
+
+impl fmt::Display for HandlerId {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        write!(out, "{}", self.0)
+    }
+}
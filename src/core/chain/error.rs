@@ -0,0 +1,43 @@
+use std::{error::Error, fmt};
+
+use crate::core::handler::HandlerError;
+
+/// An error returned by a [`crate::Chain`] created with [`crate::Chain::all_settled`].
+///
+/// Contains errors of all failed handlers in the order they were dispatched.
+/// Use [`HandlerError::get_handler_name`] to find out which handler produced an error.
+#[derive(Debug)]
+pub struct ChainError {
+    errors: Vec<HandlerError>,
+}
+
+impl ChainError {
+    pub(super) fn new(errors: Vec<HandlerError>) -> Self {
+        Self { errors }
+    }
+
+    /// Returns errors of failed handlers.
+    pub fn get_errors(&self) -> &[HandlerError] {
+        &self.errors
+    }
+
+    /// Returns errors of failed handlers consuming the error.
+    pub fn into_errors(self) -> Vec<HandlerError> {
+        self.errors
+    }
+}
+
+impl Error for ChainError {}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        write!(out, "Handlers failed: ")?;
+        for (idx, err) in self.errors.iter().enumerate() {
+            if idx > 0 {
+                write!(out, "; ")?;
+            }
+            write!(out, "'{}': {err}", err.get_handler_name().unwrap_or("unknown"))?;
+        }
+        Ok(())
+    }
+}
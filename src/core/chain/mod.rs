@@ -1,13 +1,34 @@
-use std::{any::type_name, error::Error, marker::PhantomData, sync::Arc};
+use std::{
+    any::type_name,
+    borrow::Cow,
+    collections::HashSet,
+    error::Error,
+    marker::PhantomData,
+    ops::ControlFlow,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
 
-use futures_util::future::BoxFuture;
+use futures_util::future::{BoxFuture, join_all};
 
-use crate::core::{
-    convert::TryFromInput,
-    handler::{Handler, HandlerError, HandlerInput, HandlerResult, IntoHandlerResult},
-    predicate::PredicateOutput,
+use crate::{
+    core::{
+        convert::TryFromInput,
+        describe::HandlerDescription,
+        handler::{Handler, HandlerError, HandlerInput, HandlerResult, IntoHandlerResult},
+        middleware::{Middleware, MiddlewareStack},
+        predicate::{Predicate, PredicateOutput, PredicateResult, track_command_match},
+    },
+    types::{AllowedUpdate, Command},
 };
 
+pub use self::{dynamic::*, error::*};
+
+mod dynamic;
+mod error;
+
 #[cfg(test)]
 mod tests;
 
@@ -15,26 +36,41 @@ mod tests;
 ///
 /// The chain allows you to configure multiple handlers for the [`crate::App`].
 ///
-/// There are two strategies to run handlers:
+/// There are four strategies to run handlers:
 /// - [`Self::once`] - the chain runs only a first found handler.
 /// - [`Self::all`] - the chain runs all found handlers.
+/// - [`Self::all_settled`] - the chain runs all found handlers regardless of errors.
+/// - [`Self::concurrent`] - the chain runs all found handlers at the same time.
 ///
 /// A [`Handler`] considered found when [`TryFromInput::try_from_input`] for the handler returns [`Some`].
 ///
 /// Handlers are dispatched in the order they are added.
+/// Use [`Self::with_fallback`] to run a handler when no handler is found.
+///
+/// A handler can stop the chain without an error by returning [`ControlFlow::Break`]
+/// or [`ChainResult::Stop`], a predicate can do the same by returning [`PredicateResult::Stop`].
+/// Note that handlers of a [`Self::concurrent`] chain are not stopped, see the method for more information.
 ///
-/// If a handler returns an error, all subsequent handlers will not run.
+/// If a handler returns an error, all subsequent handlers will not run,
+/// unless the chain is created using [`Self::all_settled`]
+/// or the handler is added using [`Self::with_non_critical`].
 #[derive(Clone)]
 pub struct Chain {
-    handlers: Arc<Vec<Box<dyn ChainHandler + Sync>>>,
+    handlers: Arc<Vec<Arc<dyn ChainHandler + Sync>>>,
+    fallbacks: Arc<Vec<Arc<dyn ChainHandler + Sync>>>,
+    command_fallbacks: Arc<Vec<Arc<dyn ChainHandler + Sync>>>,
     strategy: ChainStrategy,
+    middlewares: MiddlewareStack,
 }
 
 impl Chain {
     fn new(strategy: ChainStrategy) -> Self {
         Self {
             handlers: Arc::new(Vec::new()),
+            fallbacks: Arc::new(Vec::new()),
+            command_fallbacks: Arc::new(Vec::new()),
             strategy,
+            middlewares: Default::default(),
         }
     }
 
@@ -48,6 +84,27 @@ impl Chain {
         Self::new(ChainStrategy::All)
     }
 
+    /// Creates a new `Chain` that runs all given handlers concurrently.
+    ///
+    /// Use this strategy for independent handlers, e.g. logging and analytics.
+    ///
+    /// Results are processed in the order handlers are added when all handlers are finished:
+    /// the first error is returned as in [`Self::all`] strategy,
+    /// but subsequent handlers are not cancelled.
+    ///
+    /// The same applies to a stop: [`ChainResult::Stop`] returned by a handler
+    /// does not prevent other handlers from running, it only affects the result of the chain.
+    pub fn concurrent() -> Self {
+        Self::new(ChainStrategy::Concurrent)
+    }
+
+    /// Creates a new `Chain` that runs all given handlers regardless of errors.
+    ///
+    /// Errors are collected into a [`ChainError`] which is returned when all handlers are dispatched.
+    pub fn all_settled() -> Self {
+        Self::new(ChainStrategy::AllSettled)
+    }
+
     /// Adds a handler to the chain.
     ///
     /// # Arguments
@@ -64,66 +121,436 @@ impl Chain {
         O: Into<ChainResult>,
     {
         let handlers = Arc::get_mut(&mut self.handlers).expect("Can not add handler, chain is shared");
-        handlers.push(ConvertHandler::boxed(handler));
+        handlers.push(ConvertHandler::shared(handler, None, true));
+        self
+    }
+
+    /// Adds a non-critical handler to the chain.
+    ///
+    /// An error of the handler is logged and does not stop the chain.
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - The handler to add.
+    ///
+    /// # Panics
+    ///
+    /// Panics when trying to add a handler to a shared chain.
+    pub fn with_non_critical<H, I, O>(mut self, handler: H) -> Self
+    where
+        H: Handler<I, Output = O> + Sync + Clone + 'static,
+        I: TryFromInput + Sync + 'static,
+        O: Into<ChainResult>,
+    {
+        let handlers = Arc::get_mut(&mut self.handlers).expect("Can not add handler, chain is shared");
+        handlers.push(ConvertHandler::shared(handler, None, false));
+        self
+    }
+
+    /// Adds a handler with a name to the chain.
+    ///
+    /// The name is used instead of the type name of the handler
+    /// in logs, errors, metrics and [`Handler::describe`] output.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the handler.
+    /// * `handler` - The handler to add.
+    ///
+    /// # Panics
+    ///
+    /// Panics when trying to add a handler to a shared chain.
+    pub fn with_named<N, H, I, O>(mut self, name: N, handler: H) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+        H: Handler<I, Output = O> + Sync + Clone + 'static,
+        I: TryFromInput + Sync + 'static,
+        O: Into<ChainResult>,
+    {
+        let handlers = Arc::get_mut(&mut self.handlers).expect("Can not add handler, chain is shared");
+        handlers.push(ConvertHandler::shared(handler, Some(name.into()), true));
+        self
+    }
+
+    /// Adds a group of handlers sharing a predicate to the chain.
+    ///
+    /// The predicate runs once for the whole group,
+    /// members are added to a nested chain with the same strategy.
+    ///
+    /// The group is considered skipped when the predicate returns `false`
+    /// or when no member is found, so the chain continues to look for a handler.
+    ///
+    /// Example: `chain.group(AccessPredicate::new(policy), |group| group.with(ban).with(unban))`.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - A predicate handler.
+    /// * `build` - A function to add members to the group.
+    ///
+    /// # Panics
+    ///
+    /// Panics when trying to add a group to a shared chain.
+    pub fn group<P, PI, F>(mut self, predicate: P, build: F) -> Self
+    where
+        P: Handler<PI> + Sync + 'static,
+        P::Output: Into<PredicateResult>,
+        PI: TryFromInput + Sync + 'static,
+        PI::Error: 'static,
+        F: FnOnce(Chain) -> Chain,
+    {
+        let chain = build(Chain::new(self.strategy));
+        let handlers = Arc::get_mut(&mut self.handlers).expect("Can not add group, chain is shared");
+        handlers.push(Arc::new(GroupHandler {
+            predicate,
+            predicate_input: PhantomData,
+            chain,
+        }));
+        self
+    }
+
+    /// Adds a handler to run when no handler is found.
+    ///
+    /// Fallbacks are dispatched in the order they are added, only the first found fallback runs.
+    ///
+    /// Example: `chain.with_fallback(reply_unknown)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - The handler to add.
+    ///
+    /// # Panics
+    ///
+    /// Panics when trying to add a fallback to a shared chain.
+    pub fn with_fallback<H, I, O>(mut self, handler: H) -> Self
+    where
+        H: Handler<I, Output = O> + Sync + Clone + 'static,
+        I: TryFromInput + Sync + 'static,
+        O: Into<ChainResult>,
+    {
+        let fallbacks = Arc::get_mut(&mut self.fallbacks).expect("Can not add fallback, chain is shared");
+        fallbacks.push(ConvertHandler::shared(handler, None, true));
+        self
+    }
+
+    /// Adds a handler to run when the update contains a command
+    /// and the command is not matched by a [`crate::CommandPredicate`] or a [`crate::CommandRouter`].
+    ///
+    /// Unlike [`Self::with_fallback`], it runs even if other handlers are found,
+    /// so it is useful to reply to unknown commands in a chain with catch-all handlers (e.g. logging).
+    /// It does not run when a handler fails or stops the chain.
+    ///
+    /// Command fallbacks are dispatched in the order they are added, only the first found fallback runs.
+    /// When a command fallback runs, fallbacks added using [`Self::with_fallback`] do not run.
+    ///
+    /// Matches are tracked within the task running the chain,
+    /// so a predicate or a router moved to another task (e.g. by a tower `Buffer` layer) is not taken into account
+    /// and the fallback runs as if the command is unknown.
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - The handler to add.
+    ///
+    /// # Panics
+    ///
+    /// Panics when trying to add a fallback to a shared chain.
+    pub fn with_command_fallback<H, I, O>(mut self, handler: H) -> Self
+    where
+        H: Handler<I, Output = O> + Sync + Clone + 'static,
+        O: Into<ChainResult>,
+        I: TryFromInput + Sync + 'static,
+        I::Error: 'static,
+    {
+        let fallbacks = Arc::get_mut(&mut self.command_fallbacks).expect("Can not add fallback, chain is shared");
+        fallbacks.push(ConvertHandler::shared(
+            Predicate::new(AnyCommandPredicate, handler),
+            Some(Cow::Borrowed(type_name::<H>())),
+            true,
+        ));
         self
     }
 
-    async fn handle_input(&self, input: HandlerInput) -> HandlerResult {
+    /// Adds a middleware to run around the whole chain.
+    ///
+    /// Middlewares run in the order they are added.
+    ///
+    /// # Arguments
+    ///
+    /// * `middleware` - The middleware to add.
+    ///
+    /// # Panics
+    ///
+    /// Panics when trying to add a middleware to a shared chain.
+    pub fn with_middleware<M>(mut self, middleware: M) -> Self
+    where
+        M: Middleware,
+    {
+        self.middlewares.push(middleware);
+        self
+    }
+
+    async fn run(&self, input: HandlerInput) -> ChainResult {
+        if self.middlewares.is_empty() {
+            return self.run_handlers(input).await;
+        }
+        let chain = self.clone();
+        let is_skipped = Arc::new(AtomicBool::new(false));
+        let is_stopped = Arc::new(AtomicBool::new(false));
+        let result = self
+            .middlewares
+            .run(input, {
+                let is_skipped = is_skipped.clone();
+                let is_stopped = is_stopped.clone();
+                move |input| {
+                    Box::pin(async move {
+                        let result = chain.run_handlers(input).await;
+                        match result {
+                            ChainResult::Skipped => is_skipped.store(true, Ordering::SeqCst),
+                            ChainResult::Stop => is_stopped.store(true, Ordering::SeqCst),
+                            _ => {}
+                        }
+                        result.into_result()
+                    })
+                }
+            })
+            .await;
+        match result {
+            Ok(()) if is_skipped.load(Ordering::SeqCst) => ChainResult::Skipped,
+            Ok(()) if is_stopped.load(Ordering::SeqCst) => ChainResult::Stop,
+            result => ChainResult::Done(result),
+        }
+    }
+
+    async fn run_handlers(&self, input: HandlerInput) -> ChainResult {
+        let result = if self.command_fallbacks.is_empty() {
+            self.run_found(input.clone()).await
+        } else {
+            let (result, is_command_matched) = track_command_match(self.run_found(input.clone())).await;
+            if !is_command_matched
+                && matches!(result, ChainResult::Done(Ok(())) | ChainResult::Skipped)
+                && let Some(result) = run_fallbacks(&self.command_fallbacks, input.clone()).await
+            {
+                return result;
+            }
+            result
+        };
+        match result {
+            ChainResult::Skipped => run_fallbacks(&self.fallbacks, input)
+                .await
+                .unwrap_or(ChainResult::Skipped),
+            result => result,
+        }
+    }
+
+    async fn run_found(&self, input: HandlerInput) -> ChainResult {
         let handlers = self.handlers.clone();
         let strategy = self.strategy;
+        let mut is_found = false;
+        let mut errors = Vec::new();
+
+        let mut results = match strategy {
+            ChainStrategy::Concurrent => Some(
+                join_all(
+                    handlers
+                        .iter()
+                        .map(|handler| run_handler(handler.as_ref(), input.clone())),
+                )
+                .await
+                .into_iter(),
+            ),
+            _ => None,
+        };
 
         for handler in handlers.iter() {
-            let type_name = handler.get_type_name();
-            log::debug!("Running '{type_name}' handler...");
-            let result = handler.handle(input.clone()).await;
-            match result {
-                ChainResult::Done(result) => match strategy {
-                    ChainStrategy::All => match result {
-                        Ok(()) => {
-                            log::debug!("[CONTINUE] Handler '{type_name}' succeeded");
-                        }
-                        Err(err) => {
-                            log::debug!("[STOP] Handler '{type_name}' returned an error: {err}");
-                            return Err(err);
-                        }
-                    },
-                    ChainStrategy::FirstFound => {
-                        log::debug!("[STOP] First found handler: '{type_name}'");
-                        return result;
+            let (name, result) = match results {
+                Some(ref mut results) => results.next().expect("A result for each handler"),
+                None => run_handler(handler.as_ref(), input.clone()).await,
+            };
+            let (err, is_done) = match result {
+                ChainResult::Done(Ok(())) => {
+                    is_found = true;
+                    if let ChainStrategy::FirstFound = strategy {
+                        log::debug!("[STOP] First found handler: '{name}'");
+                        return ChainResult::Done(Ok(()));
                     }
-                },
+                    log::debug!("[CONTINUE] Handler '{name}' succeeded");
+                    continue;
+                }
+                ChainResult::Done(Err(err)) => {
+                    log::debug!("Handler '{name}' returned an error: {err}");
+                    (err, true)
+                }
                 ChainResult::Err(err) => {
-                    log::debug!("[STOP] Could not convert input for '{type_name}' handler: {err}");
-                    return Err(err);
+                    log::debug!("Could not convert input for '{name}' handler: {err}");
+                    (err, false)
                 }
                 ChainResult::Skipped => {
-                    log::debug!("[CONTINUE] Input not found for '{type_name}' handler");
+                    log::debug!("[CONTINUE] Input not found for '{name}' handler");
+                    continue;
+                }
+                ChainResult::Stop => {
+                    log::debug!("[STOP] Handler '{name}' stopped the chain");
+                    if errors.is_empty() {
+                        return ChainResult::Stop;
+                    }
+                    break;
+                }
+            };
+            is_found |= is_done;
+            if !handler.is_critical() {
+                log::warn!("Non-critical handler '{name}' failed: {err}");
+                if is_done && matches!(strategy, ChainStrategy::FirstFound) {
+                    return ChainResult::Done(Ok(()));
+                }
+                continue;
+            }
+            let err = err.with_handler_name(name.clone());
+            match strategy {
+                ChainStrategy::AllSettled => {
+                    log::debug!("[CONTINUE] Error of '{name}' handler collected");
+                    errors.push(err);
+                }
+                ChainStrategy::All | ChainStrategy::Concurrent | ChainStrategy::FirstFound => {
+                    log::debug!("[STOP] Handler '{name}' failed");
+                    return if is_done {
+                        ChainResult::Done(Err(err))
+                    } else {
+                        ChainResult::Err(err)
+                    };
                 }
             }
         }
-        Ok(())
+        if !errors.is_empty() {
+            return ChainResult::Done(Err(HandlerError::new(ChainError::new(errors))));
+        }
+        if is_found {
+            ChainResult::Done(Ok(()))
+        } else {
+            ChainResult::Skipped
+        }
+    }
+}
+
+async fn run_fallbacks(fallbacks: &[Arc<dyn ChainHandler + Sync>], input: HandlerInput) -> Option<ChainResult> {
+    for fallback in fallbacks {
+        let (name, result) = run_handler(fallback.as_ref(), input.clone()).await;
+        match result {
+            ChainResult::Done(result) => {
+                log::debug!("[STOP] Fallback handler: '{name}'");
+                return Some(ChainResult::Done(result.map_err(|err| err.with_handler_name(name))));
+            }
+            ChainResult::Err(err) => {
+                log::debug!("[STOP] Could not convert input for '{name}' fallback handler: {err}");
+                return Some(ChainResult::Err(err.with_handler_name(name)));
+            }
+            ChainResult::Skipped => {
+                log::debug!("[CONTINUE] Input not found for '{name}' fallback handler");
+            }
+            ChainResult::Stop => {
+                log::debug!("[STOP] Fallback handler '{name}' stopped the chain");
+                return Some(ChainResult::Stop);
+            }
+        }
     }
+    None
+}
+
+pub(in crate::core) async fn run_handler(
+    handler: &(dyn ChainHandler + Sync),
+    input: HandlerInput,
+) -> (Cow<'static, str>, ChainResult) {
+    let name = handler.get_name();
+    log::debug!("Running '{name}' handler...");
+    let future = handler.handle(input);
+    #[cfg(feature = "tracing")]
+    let future = tracing::Instrument::instrument(
+        future,
+        tracing::info_span!(
+            "handler",
+            name = name.as_ref(),
+            predicate = tracing::field::Empty,
+            decision = tracing::field::Empty,
+        ),
+    );
+    #[cfg(feature = "metrics")]
+    let started_at = std::time::Instant::now();
+    let result = future.await;
+    #[cfg(feature = "metrics")]
+    crate::metrics::registry().record_handler(&name, &result, started_at.elapsed());
+    (name, result)
 }
 
 impl Handler<HandlerInput> for Chain {
-    type Output = HandlerResult;
+    type Output = ChainResult;
 
     async fn handle(&self, input: HandlerInput) -> Self::Output {
-        self.handle_input(input).await
+        self.run(input).await
+    }
+
+    fn allowed_updates(&self) -> HashSet<AllowedUpdate> {
+        self.handlers
+            .iter()
+            .chain(self.fallbacks.iter())
+            .chain(self.command_fallbacks.iter())
+            .flat_map(|handler| handler.allowed_updates())
+            .collect()
+    }
+
+    fn describe(&self) -> HandlerDescription {
+        let strategy = match self.strategy {
+            ChainStrategy::All => "all",
+            ChainStrategy::AllSettled => "all settled",
+            ChainStrategy::Concurrent => "concurrent",
+            ChainStrategy::FirstFound => "once",
+        };
+        let handlers = self
+            .handlers
+            .iter()
+            .map(|handler| handler.describe())
+            .chain(
+                self.fallbacks
+                    .iter()
+                    .chain(self.command_fallbacks.iter())
+                    .map(|handler| handler.describe().with_predicate("fallback")),
+            )
+            .collect();
+        HandlerDescription::group("Chain", strategy, handlers)
+    }
+}
+
+#[derive(Clone)]
+struct AnyCommandPredicate;
+
+impl Handler<Command> for AnyCommandPredicate {
+    type Output = bool;
+
+    async fn handle(&self, _input: Command) -> Self::Output {
+        true
+    }
+
+    fn describe(&self) -> HandlerDescription {
+        HandlerDescription::new("any command")
     }
 }
 
 #[derive(Clone, Copy)]
 enum ChainStrategy {
     All,
+    AllSettled,
+    Concurrent,
     FirstFound,
 }
 
-trait ChainHandler: Send {
+pub(in crate::core) trait ChainHandler: Send {
     fn handle(&self, input: HandlerInput) -> BoxFuture<'static, ChainResult>;
 
-    fn get_type_name(&self) -> &'static str {
-        type_name::<Self>()
+    fn get_name(&self) -> Cow<'static, str>;
+
+    fn allowed_updates(&self) -> HashSet<AllowedUpdate>;
+
+    fn describe(&self) -> HandlerDescription;
+
+    fn is_critical(&self) -> bool {
+        true
     }
 }
 
@@ -135,6 +562,10 @@ pub enum ChainResult {
     Err(HandlerError),
     /// A handler has not been execute.
     Skipped,
+    /// The update has been handled and subsequent handlers must not run.
+    ///
+    /// Unlike an error, it is not considered a failure.
+    Stop,
 }
 
 impl From<()> for ChainResult {
@@ -143,6 +574,27 @@ impl From<()> for ChainResult {
     }
 }
 
+impl From<ControlFlow<()>> for ChainResult {
+    fn from(value: ControlFlow<()>) -> Self {
+        match value {
+            ControlFlow::Continue(()) => ChainResult::Done(Ok(())),
+            ControlFlow::Break(()) => ChainResult::Stop,
+        }
+    }
+}
+
+impl<E> From<Result<ControlFlow<()>, E>> for ChainResult
+where
+    E: Error + Send + 'static,
+{
+    fn from(result: Result<ControlFlow<()>, E>) -> Self {
+        match result {
+            Ok(value) => value.into(),
+            Err(err) => ChainResult::Done(Err(HandlerError::new(err))),
+        }
+    }
+}
+
 impl<E> From<Result<(), E>> for ChainResult
 where
     E: Error + Send + 'static,
@@ -157,6 +609,7 @@ impl From<PredicateOutput> for ChainResult {
         match output {
             PredicateOutput::True(result) => ChainResult::Done(result),
             PredicateOutput::False => ChainResult::Skipped,
+            PredicateOutput::Stop => ChainResult::Stop,
             PredicateOutput::Err(err) => ChainResult::Err(err),
         }
     }
@@ -167,22 +620,77 @@ impl IntoHandlerResult for ChainResult {
         match self {
             ChainResult::Done(result) => result,
             ChainResult::Err(err) => Err(err),
-            ChainResult::Skipped => Ok(()),
+            ChainResult::Skipped | ChainResult::Stop => Ok(()),
         }
     }
 }
 
+struct GroupHandler<P, PI> {
+    predicate: P,
+    predicate_input: PhantomData<PI>,
+    chain: Chain,
+}
+
+impl<P, PI> ChainHandler for GroupHandler<P, PI>
+where
+    P: Handler<PI> + Sync + 'static,
+    P::Output: Into<PredicateResult>,
+    PI: TryFromInput + Sync + 'static,
+    PI::Error: 'static,
+{
+    fn handle(&self, input: HandlerInput) -> BoxFuture<'static, ChainResult> {
+        let predicate = self.predicate.clone();
+        let chain = self.chain.clone();
+        Box::pin(async move {
+            let predicate_input = match PI::try_from_input(input.clone()).await {
+                Ok(Some(predicate_input)) => predicate_input,
+                Ok(None) => return ChainResult::Skipped,
+                Err(err) => return ChainResult::Err(HandlerError::convert(err)),
+            };
+            let predicate_result = predicate.handle(predicate_input).await.into();
+            #[cfg(feature = "tracing")]
+            crate::core::predicate::record_decision::<P>(&predicate_result);
+            match predicate_result {
+                PredicateResult::True => chain.run(input).await,
+                PredicateResult::False => ChainResult::Skipped,
+                PredicateResult::Stop => ChainResult::Stop,
+                PredicateResult::Err(err) => ChainResult::Err(err),
+            }
+        })
+    }
+
+    fn get_name(&self) -> Cow<'static, str> {
+        Cow::Borrowed("Chain::group")
+    }
+
+    fn allowed_updates(&self) -> HashSet<AllowedUpdate> {
+        let kinds = Handler::allowed_updates(&self.chain);
+        let mut result = self.predicate.allowed_updates();
+        result.retain(|x| kinds.contains(x));
+        result
+    }
+
+    fn describe(&self) -> HandlerDescription {
+        let predicate = self.predicate.describe();
+        Handler::describe(&self.chain).with_predicate(predicate.get_name().to_string())
+    }
+}
+
 #[derive(Clone)]
-struct ConvertHandler<H, I> {
+pub(in crate::core) struct ConvertHandler<H, I> {
     handler: H,
     input: PhantomData<I>,
+    name: Option<Cow<'static, str>>,
+    is_critical: bool,
 }
 
 impl<H, I> ConvertHandler<H, I> {
-    pub(in crate::core) fn boxed(handler: H) -> Box<Self> {
-        Box::new(Self {
+    pub(in crate::core) fn shared(handler: H, name: Option<Cow<'static, str>>, is_critical: bool) -> Arc<Self> {
+        Arc::new(Self {
             handler,
             input: PhantomData,
+            name,
+            is_critical,
         })
     }
 }
@@ -203,12 +711,31 @@ where
                     future.await.into()
                 }
                 Ok(None) => ChainResult::Skipped,
-                Err(err) => ChainResult::Err(HandlerError::new(err)),
+                Err(err) => ChainResult::Err(HandlerError::convert(err)),
             }
         })
     }
 
-    fn get_type_name(&self) -> &'static str {
-        type_name::<H>()
+    fn get_name(&self) -> Cow<'static, str> {
+        match self.name {
+            Some(ref name) => name.clone(),
+            None => Cow::Borrowed(type_name::<H>()),
+        }
+    }
+
+    fn allowed_updates(&self) -> HashSet<AllowedUpdate> {
+        self.handler.allowed_updates()
+    }
+
+    fn describe(&self) -> HandlerDescription {
+        let description = self.handler.describe();
+        match self.name {
+            Some(ref name) => description.with_name(name.clone()),
+            None => description,
+        }
+    }
+
+    fn is_critical(&self) -> bool {
+        self.is_critical
     }
 }
@@ -3,7 +3,13 @@ use std::{error::Error, fmt};
 use tokio::sync::Mutex;
 
 use crate::{
-    core::context::{Context, Ref},
+    core::{
+        context::{Context, Ref},
+        error::ErrorExt,
+        middleware::MiddlewareExt,
+        predicate::{CommandPredicate, PredicateExt},
+        router::CommandRouter,
+    },
     types::Update,
 };
 
@@ -46,6 +52,23 @@ impl fmt::Display for ErrorMock {
     }
 }
 
+macro_rules! assert_handle {
+    ($chain:expr, $update:expr, $count:expr) => {{
+        let mut context = Context::default();
+        context.insert(UpdateStore::new());
+        let context = Arc::new(context);
+        let input = HandlerInput {
+            context: context.clone(),
+            update: $update,
+            extensions: Default::default(),
+        };
+        let result = $chain.handle(input).await;
+        let count = context.get::<UpdateStore>().unwrap().count().await;
+        assert_eq!(count, $count);
+        result
+    }};
+}
+
 fn create_update() -> Update {
     serde_json::from_value(serde_json::json!({
         "update_id": 1,
@@ -62,37 +85,372 @@ fn create_update() -> Update {
 
 #[tokio::test]
 async fn chain() {
-    macro_rules! assert_handle {
+    macro_rules! assert_chain {
         ($strategy:ident, $count:expr, $($handler:expr),*) => {{
-            let mut context = Context::default();
-            context.insert(UpdateStore::new());
-            let context = Arc::new(context);
             let mut chain = Chain::$strategy();
             $(chain = chain.with($handler);)*
-            let update = create_update();
-            let input = HandlerInput {
-                context: context.clone(),
-                update
-            };
-            let result = chain.handle(input).await;
-            let count = context.get::<UpdateStore>().unwrap().count().await;
-            assert_eq!(count, $count);
-            result
+            assert_handle!(chain, create_update(), $count)
         }};
     }
 
-    let result = assert_handle!(all, 2, handler_ok, handler_error, handler_ok);
-    assert!(result.is_err());
-    let result = assert_handle!(once, 1, handler_ok, handler_error, handler_ok);
-    assert!(matches!(result, Ok(())));
+    let result = assert_chain!(all, 2, handler_ok, handler_error, handler_ok);
+    assert!(result.into_result().is_err());
+    let result = assert_chain!(once, 1, handler_ok, handler_error, handler_ok);
+    assert!(matches!(result, ChainResult::Done(Ok(()))));
+
+    let result = assert_chain!(all, 1, handler_error, handler_ok);
+    assert!(result.into_result().is_err());
+    let result = assert_chain!(once, 1, handler_error, handler_ok);
+    assert!(result.into_result().is_err());
+
+    let result = assert_chain!(all, 2, handler_ok, handler_ok);
+    assert!(matches!(result, ChainResult::Done(Ok(()))));
+    let result = assert_chain!(once, 1, handler_ok, handler_ok);
+    assert!(matches!(result, ChainResult::Done(Ok(()))));
+}
+
+#[tokio::test]
+async fn named_handler() {
+    let mut context = Context::default();
+    context.insert(UpdateStore::new());
+    let chain = Chain::all()
+        .with_named("greeting", handler_ok)
+        .with_named(String::from("failing"), handler_error);
+    let input = HandlerInput {
+        context: Arc::new(context),
+        update: create_update(),
+        extensions: Default::default(),
+    };
+    let err = chain.handle(input).await.into_result().unwrap_err();
+    assert_eq!(err.get_handler_name(), Some("failing"));
+
+    let description = chain.describe();
+    let names = description
+        .get_handlers()
+        .iter()
+        .map(|handler| handler.get_name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["greeting", "failing"]);
+}
+
+async fn handler_command(store: Ref<UpdateStore>, update: Update, _: crate::types::Command) {
+    store.push(update).await;
+}
+
+async fn predicate_true(_: Update) -> bool {
+    true
+}
+
+async fn predicate_false(_: Update) -> bool {
+    false
+}
+
+#[tokio::test]
+async fn group() {
+    // predicate returns false, so the group is skipped
+    let chain = Chain::once()
+        .group(predicate_false, |group| group.with(handler_error))
+        .with(handler_ok);
+    assert!(assert_handle!(chain, create_update(), 1).into_result().is_ok());
+
+    let chain = Chain::once()
+        .group(predicate_true, |group| group.with(handler_error))
+        .with(handler_ok);
+    let err = assert_handle!(chain, create_update(), 1).into_result().unwrap_err();
+    assert!(err.get_handler_name().unwrap().ends_with("::handler_error"));
+
+    // no member found, so the group is skipped
+    let chain = Chain::once()
+        .group(predicate_true, |group| group.with(handler_command))
+        .with(handler_ok);
+    assert!(assert_handle!(chain, create_update(), 1).into_result().is_ok());
+
+    let chain = Chain::all()
+        .group(predicate_true, |group| group.with(handler_ok).with(handler_ok))
+        .with(handler_ok);
+    assert!(assert_handle!(chain, create_update(), 3).into_result().is_ok());
+
+    let description = Chain::all()
+        .group(predicate_true, |group| group.with(handler_ok))
+        .describe();
+    let group = &description.get_handlers()[0];
+    assert_eq!(group.get_strategy(), Some("all"));
+    assert_eq!(group.get_handlers().len(), 1);
+    assert!(group.get_predicates().next().unwrap().ends_with("::predicate_true"));
+}
+
+fn create_command_update() -> Update {
+    serde_json::from_value(serde_json::json!({
+        "update_id": 1,
+        "message": {
+            "message_id": 1111,
+            "date": 0,
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "chat": {"id": 1, "type": "private", "first_name": "test"},
+            "text": "/unknown",
+            "entities": [{"type": "bot_command", "offset": 0, "length": 8}]
+        }
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn fallback() {
+    // a handler is found, so the fallback does not run
+    let chain = Chain::once().with(handler_ok).with_fallback(handler_error);
+    assert!(assert_handle!(chain, create_update(), 1).into_result().is_ok());
+
+    let chain = Chain::all()
+        .with(handler_command)
+        .with_fallback(handler_error)
+        .with_fallback(handler_ok);
+    let err = assert_handle!(chain, create_update(), 1).into_result().unwrap_err();
+    assert!(err.get_handler_name().unwrap().ends_with("::handler_error"));
+
+    let chain = Chain::once()
+        .group(predicate_false, |group| group.with(handler_ok))
+        .with_command_fallback(handler_ok);
+    assert!(assert_handle!(chain.clone(), create_update(), 0).into_result().is_ok());
+    assert!(assert_handle!(chain, create_command_update(), 1).into_result().is_ok());
+
+    // a catch-all handler is found, but the command is not matched
+    let chain = Chain::all()
+        .with(handler_ok)
+        .with(handler_command.with_predicate(CommandPredicate::new("/start")))
+        .with_command_fallback(handler_error);
+    let err = assert_handle!(chain, create_command_update(), 2)
+        .into_result()
+        .unwrap_err();
+    assert!(err.get_handler_name().unwrap().ends_with("::handler_error"));
+
+    let chain = Chain::all()
+        .with(handler_ok)
+        .with(handler_command.with_predicate(CommandPredicate::new("/unknown")))
+        .with_command_fallback(handler_error);
+    assert!(assert_handle!(chain, create_command_update(), 2).into_result().is_ok());
+
+    let chain = Chain::all()
+        .with(handler_ok)
+        .with(Chain::once().with(CommandRouter::new().with_command("/unknown", handler_command)))
+        .with_command_fallback(handler_error);
+    assert!(assert_handle!(chain, create_command_update(), 2).into_result().is_ok());
+
+    let description = Chain::once()
+        .with_fallback(handler_ok)
+        .with_command_fallback(handler_ok)
+        .describe();
+    let predicates = description
+        .get_handlers()
+        .iter()
+        .map(|handler| handler.get_predicates().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(predicates, vec![vec!["fallback"], vec!["fallback", "any command"]]);
+}
+
+#[tokio::test]
+async fn all_settled() {
+    let mut context = Context::default();
+    context.insert(UpdateStore::new());
+    let context = Arc::new(context);
+    let chain = Chain::all_settled()
+        .with_named("first", handler_error)
+        .with(handler_ok)
+        .with_non_critical(handler_error)
+        .with_named("second", handler_error);
+    let input = HandlerInput {
+        context: context.clone(),
+        update: create_update(),
+        extensions: Default::default(),
+    };
+    let err = chain.handle(input).await.into_result().unwrap_err();
+    assert_eq!(context.get::<UpdateStore>().unwrap().count().await, 4);
+    assert_eq!(
+        err.to_string(),
+        "Handlers failed: 'first': Test error; 'second': Test error"
+    );
+}
+
+#[tokio::test]
+async fn non_critical() {
+    let mut context = Context::default();
+    context.insert(UpdateStore::new());
+    let context = Arc::new(context);
+    let input = HandlerInput {
+        context: context.clone(),
+        update: create_update(),
+        extensions: Default::default(),
+    };
+    let chain = Chain::all().with_non_critical(handler_error).with(handler_ok);
+    assert!(chain.handle(input.clone()).await.into_result().is_ok());
+    assert_eq!(context.get::<UpdateStore>().unwrap().count().await, 2);
+
+    // the first found handler failed, so subsequent handlers do not run
+    let chain = Chain::once().with_non_critical(handler_error).with(handler_ok);
+    assert!(chain.handle(input).await.into_result().is_ok());
+    assert_eq!(context.get::<UpdateStore>().unwrap().count().await, 3);
+}
+
+async fn handler_barrier(barrier: Ref<Arc<tokio::sync::Barrier>>, store: Ref<UpdateStore>, update: Update) {
+    // deadlocks unless handlers run concurrently
+    barrier.wait().await;
+    store.push(update).await;
+}
+
+#[tokio::test]
+async fn concurrent() {
+    let mut context = Context::default();
+    context.insert(UpdateStore::new());
+    context.insert(Arc::new(tokio::sync::Barrier::new(2)));
+    let context = Arc::new(context);
+    let input = HandlerInput {
+        context: context.clone(),
+        update: create_update(),
+        extensions: Default::default(),
+    };
+    let chain = Chain::concurrent()
+        .with(handler_barrier)
+        .with(handler_command)
+        .with(handler_barrier);
+    let result = tokio::time::timeout(std::time::Duration::from_secs(5), chain.handle(input.clone()))
+        .await
+        .expect("Handlers are not running concurrently");
+    assert!(result.into_result().is_ok());
+    assert_eq!(context.get::<UpdateStore>().unwrap().count().await, 2);
+
+    // all handlers run, the first error is returned
+    let chain = Chain::concurrent()
+        .with_named("first", handler_error)
+        .with(handler_ok)
+        .with_named("second", handler_error);
+    let err = chain.handle(input).await.into_result().unwrap_err();
+    assert_eq!(err.get_handler_name(), Some("first"));
+    assert_eq!(context.get::<UpdateStore>().unwrap().count().await, 5);
+}
+
+#[tokio::test]
+async fn concurrent_stop() {
+    // all handlers run, the stop is returned
+    let chain = Chain::concurrent()
+        .with(handler_ok)
+        .with(handler_stop)
+        .with(handler_error);
+    assert!(matches!(assert_handle!(chain, create_update(), 3), ChainResult::Stop));
+
+    // an error of a preceding handler is returned
+    let chain = Chain::concurrent()
+        .with(handler_error)
+        .with(handler_stop)
+        .with(handler_ok);
+    assert!(assert_handle!(chain, create_update(), 3).into_result().is_err());
+}
+
+#[tokio::test]
+async fn dynamic_chain() {
+    let mut context = Context::default();
+    context.insert(UpdateStore::new());
+    let context = Arc::new(context);
+    let input = HandlerInput {
+        context: context.clone(),
+        update: create_update(),
+        extensions: Default::default(),
+    };
+    let count = || async { context.get::<UpdateStore>().unwrap().count().await };
+
+    let chain = DynamicChain::new(Chain::all().with(handler_ok));
+    let shared = chain.clone();
+    let initial_ids = chain.get_ids();
+    assert_eq!(initial_ids.len(), 1);
+
+    let id = chain.add_named("extra", handler_ok);
+    assert_eq!(shared.get_ids(), vec![initial_ids[0], id]);
+    shared.handle(input.clone()).await.into_result().unwrap();
+    assert_eq!(count().await, 2);
+
+    assert!(chain.remove(initial_ids[0]));
+    assert!(!chain.remove(initial_ids[0]));
+    shared.handle(input.clone()).await.into_result().unwrap();
+    assert_eq!(count().await, 3);
+
+    // IDs are not reused
+    let new_id = chain.add(handler_error);
+    assert_ne!(new_id, initial_ids[0]);
+    assert_eq!(shared.get_ids(), vec![id, new_id]);
+    assert!(shared.handle(input.clone()).await.into_result().is_err());
+    assert_eq!(count().await, 5);
+
+    let description = shared.describe();
+    assert_eq!(description.get_handlers()[0].get_name(), "extra");
+
+    // the whole chain is replaced, IDs are reassigned
+    let ids = chain.replace(Chain::once().with_non_critical(handler_error).with(handler_ok));
+    assert_eq!(shared.get_ids(), ids);
+    assert!(!ids.contains(&id) && !ids.contains(&new_id));
+    assert!(!chain.remove(id));
+    shared.handle(input.clone()).await.into_result().unwrap();
+    assert_eq!(count().await, 6);
+
+    assert!(chain.remove(ids[0]));
+    shared.handle(input).await.into_result().unwrap();
+    assert_eq!(count().await, 7);
+}
+
+async fn handler_stop(store: Ref<UpdateStore>, update: Update) -> std::ops::ControlFlow<()> {
+    store.push(update).await;
+    std::ops::ControlFlow::Break(())
+}
+
+async fn predicate_stop(_: Update) -> PredicateResult {
+    PredicateResult::Stop
+}
+
+#[tokio::test]
+async fn stop() {
+    let chain = Chain::all().with(handler_ok).with(handler_stop).with(handler_error);
+    assert!(matches!(assert_handle!(chain, create_update(), 2), ChainResult::Stop));
+
+    // a predicate stops the chain without running the handler
+    let chain = Chain::all()
+        .with(handler_ok.with_predicate(predicate_stop))
+        .with(handler_error)
+        .with_fallback(handler_error);
+    assert!(matches!(assert_handle!(chain, create_update(), 0), ChainResult::Stop));
+
+    // stop in a group stops the outer chain
+    let chain = Chain::all()
+        .group(predicate_true, |group| group.with(handler_stop))
+        .with(handler_error)
+        .with_middleware(|input: HandlerInput, next: crate::core::middleware::Next| next.run(input));
+    assert!(matches!(assert_handle!(chain, create_update(), 1), ChainResult::Stop));
+
+    // stop passes through decorators and nested chains
+    let chain = Chain::all()
+        .with(handler_stop.with_predicate(predicate_true))
+        .with(handler_error);
+    assert!(matches!(assert_handle!(chain, create_update(), 1), ChainResult::Stop));
+    let chain = Chain::all()
+        .with(handler_stop.on_error(|err| async move { err }))
+        .with(handler_error);
+    assert!(matches!(assert_handle!(chain, create_update(), 1), ChainResult::Stop));
+    let chain = Chain::all()
+        .with(handler_stop.with_middleware(|input: HandlerInput, next: crate::core::middleware::Next| next.run(input)))
+        .with(handler_error);
+    assert!(matches!(assert_handle!(chain, create_update(), 1), ChainResult::Stop));
+    let chain = Chain::all().with(Chain::all().with(handler_stop)).with(handler_error);
+    assert!(matches!(assert_handle!(chain, create_update(), 1), ChainResult::Stop));
 
-    let result = assert_handle!(all, 1, handler_error, handler_ok);
-    assert!(result.is_err());
-    let result = assert_handle!(once, 1, handler_error, handler_ok);
-    assert!(result.is_err());
+    // collected errors are returned
+    let chain = Chain::all_settled()
+        .with(handler_error)
+        .with(handler_stop)
+        .with(handler_ok);
+    assert!(assert_handle!(chain, create_update(), 2).into_result().is_err());
 
-    let result = assert_handle!(all, 2, handler_ok, handler_ok);
-    assert!(matches!(result, Ok(())));
-    let result = assert_handle!(once, 1, handler_ok, handler_ok);
-    assert!(matches!(result, Ok(())));
+    assert!(matches!(
+        ChainResult::from(std::ops::ControlFlow::Continue(())),
+        ChainResult::Done(Ok(()))
+    ));
+    assert!(matches!(
+        ChainResult::from(Err::<std::ops::ControlFlow<()>, ErrorMock>(ErrorMock)),
+        ChainResult::Done(Err(_))
+    ));
 }
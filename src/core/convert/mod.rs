@@ -1,11 +1,16 @@
-use std::{any::TypeId, convert::Infallible, error::Error, fmt, future::Future};
+use std::{any::TypeId, collections::HashSet, convert::Infallible, error::Error, fmt, future::Future};
 
 use crate::{
-    core::{context::Ref, handler::HandlerInput},
+    core::{
+        cache::get_or_extract,
+        context::Ref,
+        handler::HandlerInput,
+        kind::{get_all_update_kinds, get_message_update_kinds},
+    },
     types::{
-        CallbackQuery, Chat, ChatJoinRequest, ChatMemberUpdated, ChatPeerId, ChatUsername, ChosenInlineResult, Command,
-        CommandError, InlineQuery, Message, Poll, PollAnswer, PreCheckoutQuery, ShippingQuery, Text, Update, User,
-        UserPeerId, UserUsername,
+        AllowedUpdate, CallbackQuery, Chat, ChatJoinRequest, ChatMemberUpdated, ChatPeerId, ChatUsername,
+        ChosenInlineResult, Command, CommandError, InlineQuery, Message, Poll, PollAnswer, PreCheckoutQuery,
+        ShippingQuery, Text, Update, User, UserPeerId, UserUsername,
     },
 };
 
@@ -23,6 +28,13 @@ pub trait TryFromInput: Send + Sized {
     ///
     /// * `input` - An input to convert from.
     fn try_from_input(input: HandlerInput) -> impl Future<Output = Result<Option<Self>, Self::Error>> + Send;
+
+    /// Returns kinds of updates which can be converted.
+    ///
+    /// All kinds are returned by default.
+    fn allowed_updates() -> HashSet<AllowedUpdate> {
+        get_all_update_kinds()
+    }
 }
 
 impl TryFromInput for HandlerInput {
@@ -118,7 +130,12 @@ impl TryFromInput for Text {
     type Error = Infallible;
 
     async fn try_from_input(input: HandlerInput) -> Result<Option<Self>, Self::Error> {
-        Ok(Message::try_from(input.update).ok().and_then(|x| x.get_text().cloned()))
+        let Ok(message) = Message::try_from_input(input).await;
+        Ok(message.and_then(|x| x.get_text().cloned()))
+    }
+
+    fn allowed_updates() -> HashSet<AllowedUpdate> {
+        get_message_update_kinds()
     }
 }
 
@@ -126,7 +143,11 @@ impl TryFromInput for Message {
     type Error = Infallible;
 
     async fn try_from_input(input: HandlerInput) -> Result<Option<Self>, Self::Error> {
-        Ok(input.update.try_into().ok())
+        get_or_extract(async { Ok(input.update.try_into().ok()) }).await
+    }
+
+    fn allowed_updates() -> HashSet<AllowedUpdate> {
+        get_message_update_kinds()
     }
 }
 
@@ -134,14 +155,18 @@ impl TryFromInput for Command {
     type Error = CommandError;
 
     async fn try_from_input(input: HandlerInput) -> Result<Option<Self>, Self::Error> {
-        Message::try_from(input.update)
-            .ok()
-            .map(Command::try_from)
-            .transpose()
-            .or_else(|err| match err {
+        get_or_extract(async {
+            let Ok(message) = Message::try_from_input(input).await;
+            message.map(Command::try_from).transpose().or_else(|err| match err {
                 CommandError::NotFound => Ok(None),
                 err => Err(err),
             })
+        })
+        .await
+    }
+
+    fn allowed_updates() -> HashSet<AllowedUpdate> {
+        get_message_update_kinds()
     }
 }
 
@@ -151,6 +176,10 @@ impl TryFromInput for InlineQuery {
     async fn try_from_input(input: HandlerInput) -> Result<Option<Self>, Self::Error> {
         Ok(input.update.try_into().ok())
     }
+
+    fn allowed_updates() -> HashSet<AllowedUpdate> {
+        HashSet::from([AllowedUpdate::InlineQuery])
+    }
 }
 
 impl TryFromInput for ChosenInlineResult {
@@ -159,6 +188,10 @@ impl TryFromInput for ChosenInlineResult {
     async fn try_from_input(input: HandlerInput) -> Result<Option<Self>, Self::Error> {
         Ok(input.update.try_into().ok())
     }
+
+    fn allowed_updates() -> HashSet<AllowedUpdate> {
+        HashSet::from([AllowedUpdate::ChosenInlineResult])
+    }
 }
 
 impl TryFromInput for CallbackQuery {
@@ -167,6 +200,10 @@ impl TryFromInput for CallbackQuery {
     async fn try_from_input(input: HandlerInput) -> Result<Option<Self>, Self::Error> {
         Ok(input.update.try_into().ok())
     }
+
+    fn allowed_updates() -> HashSet<AllowedUpdate> {
+        HashSet::from([AllowedUpdate::CallbackQuery])
+    }
 }
 
 impl TryFromInput for ShippingQuery {
@@ -175,6 +212,10 @@ impl TryFromInput for ShippingQuery {
     async fn try_from_input(input: HandlerInput) -> Result<Option<Self>, Self::Error> {
         Ok(input.update.try_into().ok())
     }
+
+    fn allowed_updates() -> HashSet<AllowedUpdate> {
+        HashSet::from([AllowedUpdate::ShippingQuery])
+    }
 }
 
 impl TryFromInput for PreCheckoutQuery {
@@ -183,6 +224,10 @@ impl TryFromInput for PreCheckoutQuery {
     async fn try_from_input(input: HandlerInput) -> Result<Option<Self>, Self::Error> {
         Ok(input.update.try_into().ok())
     }
+
+    fn allowed_updates() -> HashSet<AllowedUpdate> {
+        HashSet::from([AllowedUpdate::PreCheckoutQuery])
+    }
 }
 
 impl TryFromInput for Poll {
@@ -191,6 +236,10 @@ impl TryFromInput for Poll {
     async fn try_from_input(input: HandlerInput) -> Result<Option<Self>, Self::Error> {
         Ok(input.update.try_into().ok())
     }
+
+    fn allowed_updates() -> HashSet<AllowedUpdate> {
+        HashSet::from([AllowedUpdate::Poll])
+    }
 }
 
 impl TryFromInput for PollAnswer {
@@ -199,6 +248,10 @@ impl TryFromInput for PollAnswer {
     async fn try_from_input(input: HandlerInput) -> Result<Option<Self>, Self::Error> {
         Ok(input.update.try_into().ok())
     }
+
+    fn allowed_updates() -> HashSet<AllowedUpdate> {
+        HashSet::from([AllowedUpdate::PollAnswer])
+    }
 }
 
 impl TryFromInput for ChatMemberUpdated {
@@ -207,6 +260,10 @@ impl TryFromInput for ChatMemberUpdated {
     async fn try_from_input(input: HandlerInput) -> Result<Option<Self>, Self::Error> {
         Ok(input.update.try_into().ok())
     }
+
+    fn allowed_updates() -> HashSet<AllowedUpdate> {
+        HashSet::from([AllowedUpdate::BotStatus, AllowedUpdate::UserStatus])
+    }
 }
 
 impl TryFromInput for ChatJoinRequest {
@@ -215,6 +272,10 @@ impl TryFromInput for ChatJoinRequest {
     async fn try_from_input(input: HandlerInput) -> Result<Option<Self>, Self::Error> {
         Ok(input.update.try_into().ok())
     }
+
+    fn allowed_updates() -> HashSet<AllowedUpdate> {
+        HashSet::from([AllowedUpdate::ChatJoinRequest])
+    }
 }
 
 macro_rules! convert_tuple {
@@ -240,6 +301,15 @@ macro_rules! convert_tuple {
                 )+
                 Ok(Some(($($T,)+)))
             }
+
+            fn allowed_updates() -> HashSet<AllowedUpdate> {
+                let mut result = get_all_update_kinds();
+                $(
+                    let kinds = <$T>::allowed_updates();
+                    result.retain(|x| kinds.contains(x));
+                )+
+                result
+            }
         }
     };
 }
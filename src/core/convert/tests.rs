@@ -45,6 +45,7 @@ async fn context_ref() {
     let input = HandlerInput {
         update,
         context: Arc::new(context),
+        extensions: Default::default(),
     };
     assert!(HandlerInput::try_from_input(input.clone()).await.unwrap().is_some());
     assert!(Update::try_from_input(input.clone()).await.unwrap().is_some());
@@ -467,6 +468,7 @@ async fn tuple() {
     let input = HandlerInput {
         update,
         context: Arc::new(context),
+        extensions: Default::default(),
     };
     assert!(HandlerInput::try_from_input(input.clone()).await.unwrap().is_some());
     assert!(Update::try_from_input(input.clone()).await.unwrap().is_some());
@@ -477,3 +479,19 @@ async fn tuple() {
             .is_some()
     );
 }
+
+#[test]
+fn allowed_updates() {
+    assert_eq!(Update::allowed_updates(), get_all_update_kinds());
+    assert_eq!(Message::allowed_updates().len(), 6);
+    assert_eq!(
+        CallbackQuery::allowed_updates(),
+        HashSet::from([AllowedUpdate::CallbackQuery])
+    );
+    assert_eq!(
+        ChatMemberUpdated::allowed_updates(),
+        HashSet::from([AllowedUpdate::BotStatus, AllowedUpdate::UserStatus])
+    );
+    assert_eq!(<(Ref<u8>, Command)>::allowed_updates(), Message::allowed_updates());
+    assert!(<(Message, CallbackQuery)>::allowed_updates().is_empty());
+}
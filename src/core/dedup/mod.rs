@@ -0,0 +1,131 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    convert::Infallible,
+    error::Error,
+    future::Future,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use futures_util::future::BoxFuture;
+
+use crate::{core::handler::HandlerError, types::Integer};
+
+#[cfg(test)]
+mod tests;
+
+tokio::task_local! {
+    static SKIP_DEDUP: ();
+}
+
+/// Runs a future in which updates are not checked by a [`DedupStore`].
+///
+/// This is useful when an update is intentionally processed again, e.g. when replaying dead letters.
+#[cfg_attr(not(feature = "dead-letter"), allow(dead_code))]
+pub(crate) async fn without_dedup<F>(future: F) -> F::Output
+where
+    F: Future,
+{
+    SKIP_DEDUP.scope((), future).await
+}
+
+/// Indicates whether the current task runs inside of [`without_dedup`] scope.
+pub(in crate::core) fn is_dedup_skipped() -> bool {
+    SKIP_DEDUP.try_with(|_| ()).is_ok()
+}
+
+/// Stores IDs of recently seen updates.
+///
+/// Use [`crate::App::with_dedup_store`] to drop updates delivered more than once.
+pub trait DedupStore: Send + Sync + 'static {
+    /// An error when accessing the store.
+    type Error: Error + Send + 'static;
+
+    /// Marks an update as seen.
+    ///
+    /// Returns `true` when the update has not been seen before.
+    ///
+    /// # Arguments
+    ///
+    /// * `update_id` - ID of the update.
+    fn insert(&self, update_id: Integer) -> impl Future<Output = Result<bool, Self::Error>> + Send;
+}
+
+/// An in-memory [`DedupStore`].
+///
+/// Keeps a bounded number of IDs, each ID is forgotten after a given lifetime.
+pub struct MemoryDedupStore {
+    capacity: usize,
+    lifetime: Duration,
+    state: Mutex<MemoryState>,
+}
+
+#[derive(Default)]
+struct MemoryState {
+    ids: HashSet<Integer>,
+    queue: VecDeque<(Integer, Instant)>,
+}
+
+impl MemoryState {
+    fn pop_front(&mut self) {
+        if let Some((id, _)) = self.queue.pop_front() {
+            self.ids.remove(&id);
+        }
+    }
+}
+
+impl MemoryDedupStore {
+    /// Creates a new `MemoryDedupStore`.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of IDs to keep.
+    /// * `lifetime` - How long an ID is kept.
+    pub fn new(capacity: usize, lifetime: Duration) -> Self {
+        Self {
+            capacity,
+            lifetime,
+            state: Default::default(),
+        }
+    }
+}
+
+impl DedupStore for MemoryDedupStore {
+    type Error = Infallible;
+
+    async fn insert(&self, update_id: Integer) -> Result<bool, Self::Error> {
+        if self.capacity == 0 {
+            return Ok(true);
+        }
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        while let Some(&(_, seen_at)) = state.queue.front() {
+            if now.duration_since(seen_at) < self.lifetime {
+                break;
+            }
+            state.pop_front();
+        }
+        if state.ids.contains(&update_id) {
+            return Ok(false);
+        }
+        while state.queue.len() >= self.capacity {
+            state.pop_front();
+        }
+        state.ids.insert(update_id);
+        state.queue.push_back((update_id, now));
+        Ok(true)
+    }
+}
+
+pub(in crate::core) trait BoxedDedupStore: Send + Sync {
+    fn insert_boxed(&self, update_id: Integer) -> BoxFuture<'_, Result<bool, HandlerError>>;
+}
+
+impl<S> BoxedDedupStore for S
+where
+    S: DedupStore,
+{
+    fn insert_boxed(&self, update_id: Integer) -> BoxFuture<'_, Result<bool, HandlerError>> {
+        Box::pin(async move { DedupStore::insert(self, update_id).await.map_err(HandlerError::new) })
+    }
+}
@@ -0,0 +1,70 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{
+    core::{
+        app::App,
+        context::{Context, Ref},
+    },
+    handler::UpdateHandler,
+    types::Update,
+};
+
+use super::*;
+
+#[tokio::test]
+async fn memory_store() {
+    let store = MemoryDedupStore::new(2, Duration::from_secs(60));
+    assert_eq!(store.insert(1).await, Ok(true));
+    assert_eq!(store.insert(1).await, Ok(false));
+    assert_eq!(store.insert(2).await, Ok(true));
+    assert_eq!(store.insert(3).await, Ok(true));
+    // capacity exceeded, the oldest ID is forgotten
+    assert_eq!(store.insert(1).await, Ok(true));
+    assert_eq!(store.insert(3).await, Ok(false));
+
+    let store = MemoryDedupStore::new(2, Duration::from_millis(10));
+    assert_eq!(store.insert(1).await, Ok(true));
+    assert_eq!(store.insert(1).await, Ok(false));
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert_eq!(store.insert(1).await, Ok(true));
+
+    let store = MemoryDedupStore::new(0, Duration::from_secs(60));
+    assert_eq!(store.insert(1).await, Ok(true));
+    assert_eq!(store.insert(1).await, Ok(true));
+}
+
+#[derive(Clone, Default)]
+struct Counter(Arc<AtomicUsize>);
+
+async fn handler(Ref(Counter(counter)): Ref<Counter>) {
+    counter.fetch_add(1, Ordering::SeqCst);
+}
+
+fn create_update(update_id: Integer) -> Update {
+    serde_json::from_value(serde_json::json!({
+        "update_id": update_id,
+        "message": {
+            "message_id": 1111,
+            "date": 0,
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "chat": {"id": 1, "type": "private", "first_name": "test"},
+            "text": "test message from private chat"
+        }
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn app() {
+    let counter = Counter::default();
+    let mut context = Context::default();
+    context.insert(counter.clone());
+    let app = App::new(context, handler).with_dedup_store(MemoryDedupStore::new(10, Duration::from_secs(60)));
+    app.handle(create_update(1)).await;
+    app.handle(create_update(1)).await;
+    app.handle(create_update(2)).await;
+    assert_eq!(counter.0.load(Ordering::SeqCst), 2);
+}
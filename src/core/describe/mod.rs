@@ -0,0 +1,152 @@
+use std::{borrow::Cow, fmt, fmt::Write};
+
+#[cfg(test)]
+mod tests;
+
+/// Describes a handler and its routing rules.
+///
+/// Use [`crate::Handler::describe`] or [`crate::App::describe`] to get a description.
+///
+/// The description can be rendered as a text tree using [`fmt::Display`]
+/// or as a Mermaid flowchart using [`Self::render_mermaid`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HandlerDescription {
+    name: Cow<'static, str>,
+    predicates: Vec<Cow<'static, str>>,
+    strategy: Option<Cow<'static, str>>,
+    handlers: Vec<HandlerDescription>,
+}
+
+impl HandlerDescription {
+    /// Creates a new `HandlerDescription`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A name of the handler.
+    pub fn new<N>(name: N) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+    {
+        Self {
+            name: name.into(),
+            predicates: Vec::new(),
+            strategy: None,
+            handlers: Vec::new(),
+        }
+    }
+
+    /// Creates a new `HandlerDescription` for a group of handlers.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A name of the group.
+    /// * `strategy` - A name of the strategy used to run handlers.
+    /// * `handlers` - Descriptions of handlers in the group.
+    pub fn group<N, S>(name: N, strategy: S, handlers: Vec<HandlerDescription>) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+        S: Into<Cow<'static, str>>,
+    {
+        Self {
+            name: name.into(),
+            predicates: Vec::new(),
+            strategy: Some(strategy.into()),
+            handlers,
+        }
+    }
+
+    /// Adds a predicate which must be satisfied to run the handler.
+    ///
+    /// Predicates are listed from the outermost to the innermost.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - A description of the predicate.
+    pub fn with_predicate<P>(mut self, predicate: P) -> Self
+    where
+        P: Into<Cow<'static, str>>,
+    {
+        self.predicates.insert(0, predicate.into());
+        self
+    }
+
+    /// Replaces a name of the handler.
+    pub(crate) fn with_name<N>(mut self, name: N) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+    {
+        self.name = name.into();
+        self
+    }
+
+    /// Returns a name of the handler.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns descriptions of predicates.
+    pub fn get_predicates(&self) -> impl Iterator<Item = &str> {
+        self.predicates.iter().map(|x| x.as_ref())
+    }
+
+    /// Returns a name of the strategy when the handler is a group.
+    pub fn get_strategy(&self) -> Option<&str> {
+        self.strategy.as_deref()
+    }
+
+    /// Returns descriptions of handlers in the group.
+    ///
+    /// The list is empty when the handler is not a group.
+    pub fn get_handlers(&self) -> &[HandlerDescription] {
+        &self.handlers
+    }
+
+    /// Renders the description as a Mermaid flowchart.
+    pub fn render_mermaid(&self) -> String {
+        let mut result = String::from("flowchart TD\n");
+        let mut counter = 0;
+        self.write_mermaid(&mut result, &mut counter);
+        result
+    }
+
+    fn get_label(&self) -> String {
+        let mut result = self.name.to_string();
+        if let Some(ref strategy) = self.strategy {
+            write!(result, " ({strategy})").unwrap();
+        }
+        if !self.predicates.is_empty() {
+            write!(result, " [{}]", self.predicates.join(", ")).unwrap();
+        }
+        result
+    }
+
+    fn write_text(&self, out: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+        writeln!(out, "{:indent$}{}", "", self.get_label(), indent = depth * 2)?;
+        for handler in &self.handlers {
+            handler.write_text(out, depth + 1)?;
+        }
+        Ok(())
+    }
+
+    fn write_mermaid(&self, out: &mut String, counter: &mut usize) -> usize {
+        let id = *counter;
+        *counter += 1;
+        let label = self
+            .get_label()
+            .replace('"', "#quot;")
+            .replace('<', "#lt;")
+            .replace('>', "#gt;");
+        writeln!(out, "    n{id}[\"{label}\"]").unwrap();
+        for handler in &self.handlers {
+            let child_id = handler.write_mermaid(out, counter);
+            writeln!(out, "    n{id} --> n{child_id}").unwrap();
+        }
+        id
+    }
+}
+
+impl fmt::Display for HandlerDescription {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        self.write_text(out, 0)
+    }
+}
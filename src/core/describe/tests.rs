@@ -0,0 +1,76 @@
+use crate::{
+    core::{
+        app::App,
+        chain::Chain,
+        context::Context,
+        handler::Handler,
+        predicate::{CommandExt, PredicateExt},
+    },
+    types::{Command, Text},
+};
+
+use super::*;
+
+async fn start(_: Command) {}
+
+async fn echo(_: Text) {}
+
+async fn has_text(_: Text) -> bool {
+    true
+}
+
+#[test]
+fn describe_chain() {
+    let chain = Chain::once()
+        .with(start.with_command("/start"))
+        .with(Chain::all().with(echo.with_command("/echo").with_predicate(has_text)));
+    let app = App::new(Context::default(), chain.clone());
+    assert_eq!(app.describe(), chain.describe());
+
+    let description = chain.describe();
+    assert_eq!(description.get_name(), "Chain");
+    assert_eq!(description.get_strategy(), Some("once"));
+    let handlers = description.get_handlers();
+    assert_eq!(handlers.len(), 2);
+    assert_eq!(handlers[0].get_name(), format!("{}::start", module_path!()));
+    assert_eq!(handlers[0].get_predicates().collect::<Vec<_>>(), vec!["command /start"]);
+    assert_eq!(handlers[0].get_strategy(), None);
+    assert_eq!(handlers[1].get_strategy(), Some("all"));
+    assert_eq!(
+        handlers[1].get_handlers()[0].get_predicates().collect::<Vec<_>>(),
+        vec![format!("{}::has_text", module_path!()).as_str(), "command /echo"]
+    );
+
+    assert_eq!(
+        description.to_string(),
+        format!(
+            "Chain (once)\n  {module}::start [command /start]\n  Chain (all)\n    {module}::echo [{module}::has_text, command /echo]\n",
+            module = module_path!()
+        )
+    );
+}
+
+#[test]
+fn render_mermaid() {
+    let description = HandlerDescription::group(
+        "Chain",
+        "once",
+        vec![
+            HandlerDescription::new("start").with_predicate("command /start"),
+            HandlerDescription::group("Chain", "all", vec![HandlerDescription::new("Handler<\"x\">")]),
+        ],
+    );
+    assert_eq!(
+        description.render_mermaid(),
+        concat!(
+            "flowchart TD\n",
+            "    n0[\"Chain (once)\"]\n",
+            "    n1[\"start [command /start]\"]\n",
+            "    n0 --> n1\n",
+            "    n2[\"Chain (all)\"]\n",
+            "    n3[\"Handler#lt;#quot;x#quot;#gt;\"]\n",
+            "    n2 --> n3\n",
+            "    n0 --> n2\n",
+        )
+    );
+}
@@ -0,0 +1,243 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures_util::future::BoxFuture;
+use tokio::sync::oneshot;
+
+use crate::{
+    core::{convert::TryFromInput, handler::HandlerInput},
+    types::{ChatPeerId, Integer, UserPeerId},
+};
+
+#[cfg(test)]
+mod tests;
+
+const DEFAULT_QUEUE_CAPACITY: usize = 100;
+const DEFAULT_REORDER_WINDOW: Duration = Duration::from_millis(10);
+
+/// Represents a key for an [`OrderedDispatch`].
+///
+/// Updates with equal keys are processed one at a time.
+pub trait DispatchKey: Clone + Eq + Hash + TryFromInput + Sync + 'static {}
+
+impl DispatchKey for ChatPeerId {}
+
+impl DispatchKey for UserPeerId {}
+
+/// Serializes processing of updates sharing the same key.
+///
+/// Updates with different keys are processed in parallel.
+/// Updates without a key (e.g. an inline query has no chat) are processed immediately.
+///
+/// Waiting updates of a key are processed in the order of their IDs,
+/// regardless of the order in which their tasks are scheduled.
+/// When a key is idle, an update waits for a short reorder window,
+/// so that updates with lower IDs received in the same batch are processed first.
+///
+/// Each key has its own bounded queue.
+/// When the queue is full, a new update for the key is discarded.
+/// A queue is removed as soon as it becomes idle.
+///
+/// Use [`crate::App::with_ordered_dispatch`] to enable it.
+pub struct OrderedDispatch<K> {
+    queues: Queues<K>,
+    queue_capacity: usize,
+    reorder_window: Duration,
+}
+
+impl OrderedDispatch<ChatPeerId> {
+    /// Creates a new `OrderedDispatch` keyed by a chat ID.
+    pub fn new() -> Self {
+        Self::keyed()
+    }
+}
+
+impl Default for OrderedDispatch<ChatPeerId> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> OrderedDispatch<K>
+where
+    K: DispatchKey,
+{
+    /// Creates a new `OrderedDispatch` keyed by `K`.
+    pub fn keyed() -> Self {
+        Self {
+            queues: Arc::new(Mutex::new(HashMap::new())),
+            queue_capacity: DEFAULT_QUEUE_CAPACITY,
+            reorder_window: DEFAULT_REORDER_WINDOW,
+        }
+    }
+
+    /// Sets a new capacity of a queue.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Maximum number of updates waiting for a key; default - 100.
+    pub fn with_queue_capacity(mut self, value: usize) -> Self {
+        self.queue_capacity = value;
+        self
+    }
+
+    /// Sets a new reorder window.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Time to wait for updates with lower IDs when a key is idle; default - 10ms.
+    pub fn with_reorder_window(mut self, value: Duration) -> Self {
+        self.reorder_window = value;
+        self
+    }
+
+    async fn acquire_key(&self, key: K, update_id: Integer) -> Option<DispatchPermit> {
+        let (ticket, receiver, is_gathering) = {
+            let mut queues = self.queues.lock().expect("Dispatch queues are poisoned");
+            let queue = queues.entry(key.clone()).or_default();
+            if queue.size > self.queue_capacity {
+                return None;
+            }
+            queue.size += 1;
+            queue.sequence += 1;
+            let position = (update_id, queue.sequence);
+            let (sender, receiver) = oneshot::channel();
+            queue.waiters.insert(position, sender);
+            let is_gathering = queue.active.is_none() && queue.gatherer.is_none();
+            if is_gathering {
+                queue.gatherer = Some(position);
+            }
+            let ticket = QueueTicket {
+                key,
+                position,
+                queues: self.queues.clone(),
+            };
+            (ticket, receiver, is_gathering)
+        };
+        if is_gathering {
+            tokio::time::sleep(self.reorder_window).await;
+            let mut queues = self.queues.lock().expect("Dispatch queues are poisoned");
+            if let Some(queue) = queues.get_mut(&ticket.key) {
+                queue.gatherer = None;
+                queue.grant_next();
+            }
+        }
+        receiver.await.ok()?;
+        Some(DispatchPermit {
+            _ticket: Some(Box::new(ticket)),
+        })
+    }
+}
+
+impl<K> Clone for OrderedDispatch<K> {
+    fn clone(&self) -> Self {
+        Self {
+            queues: self.queues.clone(),
+            queue_capacity: self.queue_capacity,
+            reorder_window: self.reorder_window,
+        }
+    }
+}
+
+type Queues<K> = Arc<Mutex<HashMap<K, KeyQueue>>>;
+
+type QueuePosition = (Integer, u64);
+
+#[derive(Default)]
+struct KeyQueue {
+    waiters: BTreeMap<QueuePosition, oneshot::Sender<()>>,
+    gatherer: Option<QueuePosition>,
+    active: Option<QueuePosition>,
+    sequence: u64,
+    size: usize,
+}
+
+impl KeyQueue {
+    fn grant_next(&mut self) {
+        while let Some((position, sender)) = self.waiters.pop_first() {
+            if sender.send(()).is_ok() {
+                self.active = Some(position);
+                return;
+            }
+        }
+        self.active = None;
+    }
+}
+
+struct QueueTicket<K>
+where
+    K: Eq + Hash,
+{
+    key: K,
+    position: QueuePosition,
+    queues: Queues<K>,
+}
+
+impl<K> Drop for QueueTicket<K>
+where
+    K: Eq + Hash,
+{
+    fn drop(&mut self) {
+        let mut queues = match self.queues.lock() {
+            Ok(queues) => queues,
+            Err(_) => return,
+        };
+        if let Some(queue) = queues.get_mut(&self.key) {
+            queue.waiters.remove(&self.position);
+            if queue.active == Some(self.position) {
+                // The key is granted to this update, pass it to the next one
+                queue.grant_next();
+            } else if queue.gatherer == Some(self.position) {
+                // The update is cancelled while gathering, let the others proceed
+                queue.gatherer = None;
+                if queue.active.is_none() {
+                    queue.grant_next();
+                }
+            }
+            queue.size -= 1;
+            if queue.size == 0 {
+                queues.remove(&self.key);
+            }
+        }
+    }
+}
+
+/// Allows to process an update until dropped.
+///
+/// An empty permit is given to updates without a key.
+pub(in crate::core) struct DispatchPermit {
+    _ticket: Option<Box<dyn Send + Sync>>,
+}
+
+impl DispatchPermit {
+    fn empty() -> Self {
+        Self { _ticket: None }
+    }
+}
+
+pub(in crate::core) trait Dispatch: Send + Sync {
+    fn acquire(&self, input: HandlerInput) -> BoxFuture<'_, Option<DispatchPermit>>;
+}
+
+impl<K> Dispatch for OrderedDispatch<K>
+where
+    K: DispatchKey,
+{
+    fn acquire(&self, input: HandlerInput) -> BoxFuture<'_, Option<DispatchPermit>> {
+        Box::pin(async move {
+            let update_id = input.update.id;
+            match K::try_from_input(input).await {
+                Ok(Some(key)) => self.acquire_key(key, update_id).await,
+                Ok(None) => Some(DispatchPermit::empty()),
+                Err(err) => {
+                    log::warn!("Could not get a dispatch key, processing the update out of order: {err}");
+                    Some(DispatchPermit::empty())
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,180 @@
+use std::time::Duration;
+
+use crate::{
+    core::{app::App, context::Ref},
+    handler::UpdateHandler,
+    types::{Integer, Update},
+};
+
+use super::*;
+
+fn create_update(update_id: Integer, chat_id: Integer) -> Update {
+    serde_json::from_value(serde_json::json!({
+        "update_id": update_id,
+        "message": {
+            "message_id": 1111,
+            "date": 0,
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "chat": {"id": chat_id, "type": "private", "first_name": "test"},
+            "text": "test message from private chat"
+        }
+    }))
+    .unwrap()
+}
+
+fn create_inline_query_update() -> Update {
+    serde_json::from_value(serde_json::json!({
+        "update_id": 1,
+        "inline_query": {
+            "id": "query-id",
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "query": "query",
+            "offset": "offset"
+        }
+    }))
+    .unwrap()
+}
+
+fn queues_len<K: DispatchKey>(dispatch: &OrderedDispatch<K>) -> usize {
+    dispatch.queues.lock().unwrap().len()
+}
+
+#[tokio::test]
+async fn permit() {
+    let dispatch = OrderedDispatch::new().with_queue_capacity(1);
+
+    let first = dispatch.acquire(create_update(1, 1).into()).await;
+    assert!(first.is_some());
+    assert_eq!(queues_len(&dispatch), 1);
+
+    let other_chat = dispatch.acquire(create_update(2, 2).into()).await;
+    assert!(other_chat.is_some());
+    assert_eq!(queues_len(&dispatch), 2);
+
+    let no_key = dispatch.acquire(create_inline_query_update().into()).await;
+    assert!(matches!(no_key, Some(DispatchPermit { _ticket: None })));
+
+    let waiting = dispatch.clone();
+    let waiting = tokio::spawn(async move { waiting.acquire(create_update(3, 1).into()).await.is_some() });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert!(!waiting.is_finished());
+
+    // capacity exceeded
+    assert!(dispatch.acquire(create_update(4, 1).into()).await.is_none());
+
+    drop(first);
+    assert!(waiting.await.unwrap());
+
+    drop(other_chat);
+    assert_eq!(queues_len(&dispatch), 0);
+}
+
+#[derive(Clone, Default)]
+struct Events {
+    items: Arc<Mutex<Vec<String>>>,
+}
+
+async fn handler(events: Ref<Events>, update: Update) {
+    events.items.lock().unwrap().push(format!("start {}", update.id));
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    events.items.lock().unwrap().push(format!("end {}", update.id));
+}
+
+#[tokio::test]
+async fn app() {
+    let events = Events::default();
+    let mut context = crate::core::context::Context::default();
+    context.insert(events.clone());
+    let app = App::new(context, handler).with_ordered_dispatch(OrderedDispatch::new());
+
+    futures_util::future::join(app.handle(create_update(1, 1)), app.handle(create_update(2, 1))).await;
+    assert_eq!(
+        *events.items.lock().unwrap(),
+        vec!["start 1", "end 1", "start 2", "end 2"]
+    );
+}
+
+async fn order_handler(events: Ref<Events>, update: Update) {
+    events.items.lock().unwrap().push(update.id.to_string());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn app_order() {
+    for _ in 0..20 {
+        let events = Events::default();
+        let mut context = crate::core::context::Context::default();
+        context.insert(events.clone());
+        let app = App::new(context, order_handler).with_ordered_dispatch(OrderedDispatch::new());
+        // Spawn a task per update like LongPoll does
+        let tasks: Vec<_> = (1..=10)
+            .map(|update_id| {
+                let app = app.clone();
+                tokio::spawn(async move { app.handle(create_update(update_id, 1)).await })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+        let expected: Vec<String> = (1..=10).map(|x: Integer| x.to_string()).collect();
+        assert_eq!(*events.items.lock().unwrap(), expected);
+    }
+}
+
+#[tokio::test]
+async fn cancelled_waiter() {
+    let dispatch = OrderedDispatch::new().with_reorder_window(Duration::ZERO);
+    let first = dispatch.acquire(create_update(1, 1).into()).await;
+    assert!(first.is_some());
+
+    let cancelled = dispatch.clone();
+    let cancelled = tokio::spawn(async move { cancelled.acquire(create_update(2, 1).into()).await.is_some() });
+    let waiting = dispatch.clone();
+    let waiting = tokio::spawn(async move { waiting.acquire(create_update(3, 1).into()).await.is_some() });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    cancelled.abort();
+    assert!(cancelled.await.unwrap_err().is_cancelled());
+
+    drop(first);
+    assert!(waiting.await.unwrap());
+    assert_eq!(queues_len(&dispatch), 0);
+}
+
+#[test]
+fn dropped_receiver() {
+    let queues: Queues<Integer> = Default::default();
+    let mut receivers = Vec::new();
+    {
+        let mut queues = queues.lock().unwrap();
+        let queue = queues.entry(1).or_default();
+        for update_id in 1..=4 {
+            let (sender, receiver) = oneshot::channel();
+            queue.waiters.insert((update_id, update_id as u64), sender);
+            queue.size += 1;
+            receivers.push(receiver);
+        }
+        queue.grant_next();
+    }
+    let ticket = |update_id: Integer| QueueTicket {
+        key: 1,
+        position: (update_id, update_id as u64),
+        queues: queues.clone(),
+    };
+    let mut receivers = receivers.into_iter();
+    let mut first = receivers.next().unwrap();
+    let second = receivers.next().unwrap();
+    let mut third = receivers.next().unwrap();
+    let mut fourth = receivers.next().unwrap();
+    assert!(first.try_recv().is_ok());
+
+    // the receiver of the second update is dropped before its ticket, so the key goes to the third one
+    drop(second);
+    drop(ticket(1));
+    assert!(third.try_recv().is_ok());
+    drop(ticket(2));
+    assert!(fourth.try_recv().is_err());
+
+    drop(ticket(3));
+    assert!(fourth.try_recv().is_ok());
+    drop(ticket(4));
+    assert!(queues.lock().unwrap().is_empty());
+}
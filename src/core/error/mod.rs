@@ -1,8 +1,13 @@
-use std::{future::Future, marker::PhantomData};
+use std::{collections::HashSet, future::Future, marker::PhantomData};
 
-use crate::core::{
-    convert::TryFromInput,
-    handler::{Handler, HandlerError, HandlerInput, HandlerResult, IntoHandlerResult},
+use crate::{
+    core::{
+        chain::ChainResult,
+        convert::TryFromInput,
+        describe::HandlerDescription,
+        handler::{Handler, HandlerError, HandlerInput},
+    },
+    types::AllowedUpdate,
 };
 
 #[cfg(test)]
@@ -51,21 +56,30 @@ where
     H: Handler<HI> + Sync + 'static,
     HI: TryFromInput + Sync,
     HI::Error: 'static,
-    H::Output: IntoHandlerResult,
+    H::Output: Into<ChainResult>,
 {
-    type Output = HandlerResult;
+    type Output = ChainResult;
 
     async fn handle(&self, input: HandlerInput) -> Self::Output {
         let future = HI::try_from_input(input);
         match future.await {
-            Ok(Some(input)) => match self.handler.handle(input).await.into_result() {
-                Err(err) => Err(self.error_handler.handle(err).await),
+            Ok(Some(input)) => match self.handler.handle(input).await.into() {
+                ChainResult::Done(Err(err)) => ChainResult::Done(Err(self.error_handler.handle(err).await)),
+                ChainResult::Err(err) => ChainResult::Err(self.error_handler.handle(err).await),
                 result => result,
             },
-            Ok(None) => Ok(()),
-            Err(err) => Err(self.error_handler.handle(HandlerError::new(err)).await),
+            Ok(None) => ChainResult::Skipped,
+            Err(err) => ChainResult::Err(self.error_handler.handle(HandlerError::convert(err)).await),
         }
     }
+
+    fn allowed_updates(&self) -> HashSet<AllowedUpdate> {
+        self.handler.allowed_updates()
+    }
+
+    fn describe(&self) -> HandlerDescription {
+        self.handler.describe()
+    }
 }
 
 /// Allows to process errors returned by handlers.
@@ -2,7 +2,10 @@ use std::{error::Error, fmt, sync::Arc};
 
 use tokio::sync::Mutex;
 
-use crate::{core::handler::HandlerInput, types::Update};
+use crate::{
+    core::handler::{HandlerInput, IntoHandlerResult},
+    types::Update,
+};
 
 use super::*;
 
@@ -57,6 +60,6 @@ async fn error_decorator() {
     let update = create_update();
     let input = HandlerInput::from(update);
     let result = handler.handle(input).await;
-    assert!(result.is_err());
+    assert!(result.into_result().is_err());
     assert!(*condition.value.lock().await)
 }
@@ -0,0 +1,39 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    sync::Arc,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Values attached to a [`crate::HandlerInput`] while it is dispatched.
+///
+/// Unlike a [`crate::Context`], extensions belong to a single input,
+/// e.g. [`crate::CallbackRouter`] attaches [`crate::CallbackArgs`] for a matched route.
+/// Extensions are passed along with the input, so they are available
+/// even if a handler runs in another task.
+#[derive(Clone, Debug, Default)]
+pub struct Extensions {
+    items: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl Extensions {
+    /// Returns an immutable reference to the value of type `T`.
+    pub fn get<T: 'static>(&self) -> Option<&T> {
+        self.items
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref())
+    }
+
+    /// Inserts a value of type `T` into the extensions.
+    ///
+    /// Replaces a previously inserted value of the same type.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to insert.
+    pub fn insert<T: Send + Sync + 'static>(&mut self, value: T) {
+        self.items.insert(TypeId::of::<T>(), Arc::new(value));
+    }
+}
@@ -0,0 +1,14 @@
+use super::*;
+
+#[test]
+fn extensions() {
+    let mut extensions = Extensions::default();
+    assert!(extensions.get::<u8>().is_none());
+    extensions.insert(1u8);
+    let copy = extensions.clone();
+    extensions.insert(2u8);
+    extensions.insert("value");
+    assert_eq!(extensions.get::<u8>(), Some(&2));
+    assert_eq!(extensions.get::<&str>(), Some(&"value"));
+    assert_eq!(copy.get::<u8>(), Some(&1));
+}
@@ -1,8 +1,10 @@
-use std::{error::Error, fmt, future::Future, sync::Arc};
+use std::{
+    any::type_name, borrow::Cow, collections::HashSet, error::Error, fmt, future::Future, ops::ControlFlow, sync::Arc,
+};
 
 use crate::{
-    core::{context::Context, convert::TryFromInput},
-    types::Update,
+    core::{context::Context, convert::TryFromInput, describe::HandlerDescription, extensions::Extensions},
+    types::{AllowedUpdate, Update},
 };
 
 #[cfg(test)]
@@ -30,6 +32,22 @@ where
     ///
     /// See [`TryFromInput`] trait implementations for a list of supported types.
     fn handle(&self, input: I) -> impl Future<Output = Self::Output> + Send;
+
+    /// Returns kinds of updates the handler is able to process.
+    ///
+    /// By default, the kinds are defined by [`TryFromInput::allowed_updates`] of the input.
+    /// Decorators should return kinds of the decorated handler.
+    fn allowed_updates(&self) -> HashSet<AllowedUpdate> {
+        I::allowed_updates()
+    }
+
+    /// Returns a description of the handler.
+    ///
+    /// By default, the description contains a type name of the handler.
+    /// Decorators should return a description of the decorated handler.
+    fn describe(&self) -> HandlerDescription {
+        HandlerDescription::new(type_name::<Self>())
+    }
 }
 
 macro_rules! impl_fn {
@@ -69,6 +87,8 @@ pub struct HandlerInput {
     pub update: Update,
     /// A context with shared state.
     pub context: Arc<Context>,
+    /// Values attached to the input while it is dispatched.
+    pub extensions: Extensions,
 }
 
 impl From<Update> for HandlerInput {
@@ -76,12 +96,17 @@ impl From<Update> for HandlerInput {
         HandlerInput {
             update,
             context: Arc::new(Default::default()),
+            extensions: Default::default(),
         }
     }
 }
 
 /// An error returned by a [`Handler`] trait implementation.
-pub struct HandlerError(Box<dyn Error + Send>);
+pub struct HandlerError {
+    source: Box<dyn Error + Send>,
+    handler_name: Option<Cow<'static, str>>,
+    is_convert_error: bool,
+}
 
 impl HandlerError {
     /// Creates a new `HandlerError`.
@@ -93,25 +118,73 @@ impl HandlerError {
     where
         E: Error + Send + 'static,
     {
-        Self(Box::new(err))
+        let source: Box<dyn Error + Send> = Box::new(err);
+        // Keep the handler name when a HandlerError is converted again
+        match source.downcast::<HandlerError>() {
+            Ok(err) => *err,
+            Err(source) => Self {
+                source,
+                handler_name: None,
+                is_convert_error: false,
+            },
+        }
+    }
+
+    /// Creates a new `HandlerError` for an input which could not be converted.
+    ///
+    /// # Arguments
+    ///
+    /// * `err` - The error returned by [`TryFromInput::try_from_input`].
+    pub(crate) fn convert<E>(err: E) -> Self
+    where
+        E: Error + Send + 'static,
+    {
+        let mut result = Self::new(err);
+        result.is_convert_error = true;
+        result
+    }
+
+    /// Returns a name of the handler that produced the error.
+    ///
+    /// The name is available when the handler runs inside a [`crate::Chain`].
+    pub fn get_handler_name(&self) -> Option<&str> {
+        self.handler_name.as_deref()
+    }
+
+    /// Indicates whether the error occurred when converting an input of the handler.
+    ///
+    /// See [`TryFromInput`] for more information.
+    pub fn is_convert_error(&self) -> bool {
+        self.is_convert_error
+    }
+
+    /// Sets a name of the handler if it is not set yet.
+    pub(crate) fn with_handler_name<N>(mut self, name: N) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+    {
+        if self.handler_name.is_none() {
+            self.handler_name = Some(name.into());
+        }
+        self
     }
 }
 
 impl fmt::Debug for HandlerError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Debug::fmt(&self.0, f)
+        fmt::Debug::fmt(&self.source, f)
     }
 }
 
 impl fmt::Display for HandlerError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Display::fmt(&self.0, f)
+        fmt::Display::fmt(&self.source, f)
     }
 }
 
 impl Error for HandlerError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
-        self.0.source()
+        self.source.source()
     }
 }
 
@@ -138,3 +211,18 @@ where
         self.map_err(HandlerError::new)
     }
 }
+
+impl IntoHandlerResult for ControlFlow<()> {
+    fn into_result(self) -> HandlerResult {
+        Ok(())
+    }
+}
+
+impl<E> IntoHandlerResult for Result<ControlFlow<()>, E>
+where
+    E: Error + Send + 'static,
+{
+    fn into_result(self) -> HandlerResult {
+        self.map(|_| ()).map_err(HandlerError::new)
+    }
+}
@@ -37,3 +37,14 @@ fn convert() {
     assert!(matches!(Ok::<(), ExampleError>(()).into_result(), Ok(())));
     assert!(Err::<(), ExampleError>(ExampleError).into_result().is_err());
 }
+
+#[test]
+fn handler_name() {
+    let err = HandlerError::new(ExampleError);
+    assert!(err.get_handler_name().is_none());
+    let err = err.with_handler_name("first").with_handler_name("second");
+    assert_eq!(err.get_handler_name(), Some("first"));
+    let err = Err::<(), HandlerError>(err).into_result().unwrap_err();
+    assert_eq!(err.get_handler_name(), Some("first"));
+    assert_eq!(err.to_string(), "Example error");
+}
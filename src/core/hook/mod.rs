@@ -0,0 +1,87 @@
+use std::{future::Future, sync::Arc};
+
+use futures_util::future::BoxFuture;
+
+use crate::core::{
+    context::Context,
+    handler::{HandlerResult, IntoHandlerResult},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Allows to run a code when an [`crate::App`] starts or stops.
+///
+/// Use [`crate::App::on_startup`] and [`crate::App::on_shutdown`] to register a hook.
+pub trait Hook: Send + Sync + 'static {
+    /// Runs the hook.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The context of the app.
+    fn run(&self, context: Arc<Context>) -> impl Future<Output = HandlerResult> + Send;
+}
+
+impl<F, R> Hook for F
+where
+    F: Fn(Arc<Context>) -> R + Send + Sync + 'static,
+    R: Future + Send,
+    R::Output: IntoHandlerResult,
+{
+    async fn run(&self, context: Arc<Context>) -> HandlerResult {
+        (self)(context).await.into_result()
+    }
+}
+
+trait BoxedHook: Send + Sync {
+    fn run(&self, context: Arc<Context>) -> BoxFuture<'_, HandlerResult>;
+}
+
+impl<H> BoxedHook for H
+where
+    H: Hook,
+{
+    fn run(&self, context: Arc<Context>) -> BoxFuture<'_, HandlerResult> {
+        Box::pin(Hook::run(self, context))
+    }
+}
+
+type HookList = Vec<Arc<dyn BoxedHook>>;
+
+/// Stores hooks of an app.
+#[derive(Default)]
+pub(in crate::core) struct Hooks {
+    startup: HookList,
+    shutdown: HookList,
+}
+
+impl Hooks {
+    pub(in crate::core) fn add_startup<H: Hook>(&mut self, hook: H) {
+        self.startup.push(Arc::new(hook));
+    }
+
+    pub(in crate::core) fn add_shutdown<H: Hook>(&mut self, hook: H) {
+        self.shutdown.push(Arc::new(hook));
+    }
+
+    /// Runs startup hooks in the order they are added.
+    ///
+    /// Stops at the first error.
+    pub(in crate::core) async fn run_startup(&self, context: Arc<Context>) -> HandlerResult {
+        for hook in self.startup.iter() {
+            hook.run(context.clone()).await?;
+        }
+        Ok(())
+    }
+
+    /// Runs all shutdown hooks in the order they are added.
+    ///
+    /// Errors are logged.
+    pub(in crate::core) async fn run_shutdown(&self, context: Arc<Context>) {
+        for hook in self.shutdown.iter() {
+            if let Err(err) = hook.run(context.clone()).await {
+                log::error!("Shutdown hook failed: {err}");
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::{
+    error::Error,
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::core::{app::App, chain::Chain, handler::HandlerInput};
+
+use super::*;
+
+#[derive(Clone, Default)]
+struct Hooks {
+    called: Arc<Mutex<Vec<&'static str>>>,
+}
+
+#[derive(Debug)]
+struct ExampleError;
+
+impl fmt::Display for ExampleError {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        write!(out, "Example error")
+    }
+}
+
+impl Error for ExampleError {}
+
+fn create_hook(name: &'static str, is_ok: bool) -> impl Hook {
+    move |context: Arc<Context>| async move {
+        context.get::<Hooks>().unwrap().called.lock().unwrap().push(name);
+        if is_ok { Ok(()) } else { Err(ExampleError) }
+    }
+}
+
+fn create_app() -> (App<Chain, HandlerInput>, Hooks) {
+    let hooks = Hooks::default();
+    let mut context = Context::default();
+    context.insert(hooks.clone());
+    (App::new(context, Chain::all()), hooks)
+}
+
+#[tokio::test]
+async fn startup() {
+    let (app, hooks) = create_app();
+    let app = app
+        .on_startup(create_hook("first", true))
+        .on_startup(create_hook("second", true));
+    app.run_startup_hooks().await.unwrap();
+    assert_eq!(*hooks.called.lock().unwrap(), vec!["first", "second"]);
+
+    let (app, hooks) = create_app();
+    let app = app
+        .on_startup(create_hook("first", false))
+        .on_startup(create_hook("second", true));
+    assert!(app.run_startup_hooks().await.is_err());
+    assert_eq!(*hooks.called.lock().unwrap(), vec!["first"]);
+}
+
+#[tokio::test]
+async fn shutdown() {
+    let (app, hooks) = create_app();
+    let app = app
+        .on_startup(create_hook("startup", true))
+        .on_shutdown(create_hook("first", false))
+        .on_shutdown(create_hook("second", true));
+    app.get_shutdown_handle().shutdown(Duration::from_secs(1)).await;
+    assert_eq!(*hooks.called.lock().unwrap(), vec!["first", "second"]);
+}
+
+#[test]
+#[should_panic(expected = "Can not add hook, app is shared")]
+fn shared_app() {
+    let (app, _) = create_app();
+    let _handle = app.get_shutdown_handle();
+    let _app = app.on_startup(create_hook("startup", true));
+}
@@ -0,0 +1,130 @@
+use std::collections::HashSet;
+
+use crate::types::{AllowedUpdate, Update, UpdateType};
+
+#[cfg(test)]
+mod tests;
+
+/// Provides information about a kind of an update.
+pub trait UpdateKindExt {
+    /// Returns the kind of the update.
+    ///
+    /// Returns `None` when the update type is unknown.
+    fn get_kind(&self) -> Option<AllowedUpdate>;
+
+    /// Returns a name of the kind as it is used in the Telegram Bot API, e.g. `message`.
+    ///
+    /// Returns `unknown` when the update type is unknown.
+    fn get_kind_name(&self) -> &'static str {
+        self.get_kind().map(get_update_kind_name).unwrap_or("unknown")
+    }
+}
+
+impl UpdateKindExt for Update {
+    fn get_kind(&self) -> Option<AllowedUpdate> {
+        use self::UpdateType::*;
+        Some(match self.update_type {
+            BotStatus(_) => AllowedUpdate::BotStatus,
+            BusinessConnection(_) => AllowedUpdate::BusinessConnection,
+            BusinessMessage(_) => AllowedUpdate::BusinessMessage,
+            CallbackQuery(_) => AllowedUpdate::CallbackQuery,
+            ChannelPost(_) => AllowedUpdate::ChannelPost,
+            ChatBoostRemoved(_) => AllowedUpdate::ChatBoostRemoved,
+            ChatBoostUpdated(_) => AllowedUpdate::ChatBoostUpdated,
+            ChatJoinRequest(_) => AllowedUpdate::ChatJoinRequest,
+            ChosenInlineResult(_) => AllowedUpdate::ChosenInlineResult,
+            DeletedBusinessMessages(_) => AllowedUpdate::DeletedBusinessMessages,
+            EditedBusinessMessage(_) => AllowedUpdate::EditedBusinessMessage,
+            EditedChannelPost(_) => AllowedUpdate::EditedChannelPost,
+            EditedMessage(_) => AllowedUpdate::EditedMessage,
+            InlineQuery(_) => AllowedUpdate::InlineQuery,
+            Message(_) => AllowedUpdate::Message,
+            MessageReaction(_) => AllowedUpdate::MessageReaction,
+            MessageReactionCount(_) => AllowedUpdate::MessageReactionCount,
+            Poll(_) => AllowedUpdate::Poll,
+            PollAnswer(_) => AllowedUpdate::PollAnswer,
+            PreCheckoutQuery(_) => AllowedUpdate::PreCheckoutQuery,
+            PurchasedPaidMedia(_) => AllowedUpdate::PurchasedPaidMedia,
+            ShippingQuery(_) => AllowedUpdate::ShippingQuery,
+            UserStatus(_) => AllowedUpdate::UserStatus,
+            Unknown(_) => return None,
+        })
+    }
+}
+
+/// Returns a name of an update kind as it is used in the Telegram Bot API.
+///
+/// # Arguments
+///
+/// * `kind` - The kind of an update.
+pub fn get_update_kind_name(kind: AllowedUpdate) -> &'static str {
+    use self::AllowedUpdate::*;
+    match kind {
+        BotStatus => "my_chat_member",
+        BusinessConnection => "business_connection",
+        BusinessMessage => "business_message",
+        CallbackQuery => "callback_query",
+        ChannelPost => "channel_post",
+        ChatBoostRemoved => "removed_chat_boost",
+        ChatBoostUpdated => "chat_boost",
+        ChatJoinRequest => "chat_join_request",
+        ChosenInlineResult => "chosen_inline_result",
+        DeletedBusinessMessages => "deleted_business_messages",
+        EditedBusinessMessage => "edited_business_message",
+        EditedChannelPost => "edited_channel_post",
+        EditedMessage => "edited_message",
+        InlineQuery => "inline_query",
+        Message => "message",
+        MessageReaction => "message_reaction",
+        MessageReactionCount => "message_reaction_count",
+        Poll => "poll",
+        PollAnswer => "poll_answer",
+        PreCheckoutQuery => "pre_checkout_query",
+        PurchasedPaidMedia => "purchased_paid_media",
+        ShippingQuery => "shipping_query",
+        UserStatus => "chat_member",
+    }
+}
+
+/// Returns all kinds of updates.
+pub fn get_all_update_kinds() -> HashSet<AllowedUpdate> {
+    use self::AllowedUpdate::*;
+    HashSet::from([
+        BotStatus,
+        BusinessConnection,
+        BusinessMessage,
+        CallbackQuery,
+        ChannelPost,
+        ChatBoostRemoved,
+        ChatBoostUpdated,
+        ChatJoinRequest,
+        ChosenInlineResult,
+        DeletedBusinessMessages,
+        EditedBusinessMessage,
+        EditedChannelPost,
+        EditedMessage,
+        InlineQuery,
+        Message,
+        MessageReaction,
+        MessageReactionCount,
+        Poll,
+        PollAnswer,
+        PreCheckoutQuery,
+        PurchasedPaidMedia,
+        ShippingQuery,
+        UserStatus,
+    ])
+}
+
+/// Returns kinds of updates containing a message.
+pub(crate) fn get_message_update_kinds() -> HashSet<AllowedUpdate> {
+    use self::AllowedUpdate::*;
+    HashSet::from([
+        BusinessMessage,
+        ChannelPost,
+        EditedBusinessMessage,
+        EditedChannelPost,
+        EditedMessage,
+        Message,
+    ])
+}
@@ -0,0 +1,39 @@
+use super::*;
+
+#[test]
+fn update_kind() {
+    let update: Update = serde_json::from_value(serde_json::json!({
+        "update_id": 1,
+        "message": {
+            "message_id": 1111,
+            "date": 0,
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "chat": {"id": 1, "type": "private", "first_name": "test"},
+            "text": "test"
+        }
+    }))
+    .unwrap();
+    assert_eq!(update.get_kind(), Some(AllowedUpdate::Message));
+    assert_eq!(update.get_kind_name(), "message");
+
+    let update: Update = serde_json::from_value(serde_json::json!({
+        "update_id": 1,
+        "unknown_update": {}
+    }))
+    .unwrap();
+    assert_eq!(update.get_kind(), None);
+    assert_eq!(update.get_kind_name(), "unknown");
+}
+
+#[test]
+fn kind_name() {
+    for kind in [
+        AllowedUpdate::BotStatus,
+        AllowedUpdate::ChatBoostRemoved,
+        AllowedUpdate::EditedMessage,
+        AllowedUpdate::UserStatus,
+    ] {
+        let expected = serde_json::to_value(kind).unwrap();
+        assert_eq!(get_update_kind_name(kind), expected.as_str().unwrap());
+    }
+}
@@ -0,0 +1,180 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        Arc, Mutex, MutexGuard,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use futures_util::future::{AbortHandle, AbortRegistration};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+#[cfg(test)]
+mod tests;
+
+/// Limits the number of updates processed by an [`crate::App`] at the same time.
+///
+/// The limit covers input conversion and handler execution,
+/// so it can be used to protect a limited resource like a database connection pool.
+///
+/// Use [`crate::App::with_concurrency_limit`] to enable it.
+///
+/// The limit can be cloned in order to obtain counters later.
+#[derive(Clone)]
+pub struct ConcurrencyLimit {
+    semaphore: Arc<Semaphore>,
+    policy: LimitPolicy,
+    state: Arc<LimitState>,
+}
+
+impl ConcurrencyLimit {
+    fn new(limit: usize, policy: LimitPolicy) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(limit)),
+            policy,
+            state: Default::default(),
+        }
+    }
+
+    /// Creates a new `ConcurrencyLimit` with the wait policy.
+    ///
+    /// When the limit is reached, an update waits until another update is processed.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - Maximum number of updates processed at the same time.
+    pub fn wait(limit: usize) -> Self {
+        Self::new(limit, LimitPolicy::Wait)
+    }
+
+    /// Creates a new `ConcurrencyLimit` with the drop oldest policy.
+    ///
+    /// When the limit is reached, the oldest update in progress is cancelled
+    /// in favor of a new one.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - Maximum number of updates processed at the same time.
+    pub fn drop_oldest(limit: usize) -> Self {
+        Self::new(limit, LimitPolicy::DropOldest)
+    }
+
+    /// Creates a new `ConcurrencyLimit` with the reject policy.
+    ///
+    /// When the limit is reached, a new update is discarded.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - Maximum number of updates processed at the same time.
+    pub fn reject(limit: usize) -> Self {
+        Self::new(limit, LimitPolicy::Reject)
+    }
+
+    /// Returns current values of the counters.
+    pub fn get_counters(&self) -> ConcurrencyCounters {
+        ConcurrencyCounters {
+            waited: self.state.waited.load(Ordering::Relaxed),
+            dropped: self.state.dropped.load(Ordering::Relaxed),
+            rejected: self.state.rejected.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Acquires a permit to process an update.
+    ///
+    /// Returns `None` when the update must be discarded.
+    pub(in crate::core) async fn acquire(&self) -> Option<LimitPermit> {
+        let permit = match self.semaphore.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => match self.policy {
+                LimitPolicy::Wait => {
+                    self.state.waited.fetch_add(1, Ordering::Relaxed);
+                    self.acquire_owned().await?
+                }
+                LimitPolicy::DropOldest => {
+                    if let Some(handle) = self.state.pop_oldest() {
+                        handle.abort();
+                        self.state.dropped.fetch_add(1, Ordering::Relaxed);
+                    }
+                    self.acquire_owned().await?
+                }
+                LimitPolicy::Reject => {
+                    self.state.rejected.fetch_add(1, Ordering::Relaxed);
+                    return None;
+                }
+            },
+        };
+        let (handle, registration) = AbortHandle::new_pair();
+        let id = self.state.next_id.fetch_add(1, Ordering::Relaxed);
+        self.state.lock().insert(id, handle);
+        Some(LimitPermit {
+            id,
+            state: self.state.clone(),
+            registration: Some(registration),
+            _permit: permit,
+        })
+    }
+
+    async fn acquire_owned(&self) -> Option<OwnedSemaphorePermit> {
+        self.semaphore.clone().acquire_owned().await.ok()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum LimitPolicy {
+    Wait,
+    DropOldest,
+    Reject,
+}
+
+/// Represents values of [`ConcurrencyLimit`] counters.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ConcurrencyCounters {
+    /// Number of updates waited for a free slot.
+    pub waited: u64,
+    /// Number of updates cancelled in favor of newer ones.
+    pub dropped: u64,
+    /// Number of discarded updates.
+    pub rejected: u64,
+}
+
+#[derive(Default)]
+struct LimitState {
+    next_id: AtomicU64,
+    running: Mutex<BTreeMap<u64, AbortHandle>>,
+    waited: AtomicU64,
+    dropped: AtomicU64,
+    rejected: AtomicU64,
+}
+
+impl LimitState {
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<u64, AbortHandle>> {
+        self.running.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn pop_oldest(&self) -> Option<AbortHandle> {
+        self.lock().pop_first().map(|(_, handle)| handle)
+    }
+}
+
+/// Allows to process an update until dropped.
+pub(in crate::core) struct LimitPermit {
+    id: u64,
+    state: Arc<LimitState>,
+    registration: Option<AbortRegistration>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl LimitPermit {
+    /// Returns a registration for the update processing future.
+    ///
+    /// The future is aborted when the update is dropped in favor of a newer one.
+    pub(in crate::core) fn take_registration(&mut self) -> AbortRegistration {
+        self.registration.take().expect("Registration is already taken")
+    }
+}
+
+impl Drop for LimitPermit {
+    fn drop(&mut self) {
+        self.state.lock().remove(&self.id);
+    }
+}
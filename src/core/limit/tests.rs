@@ -0,0 +1,99 @@
+use std::{sync::atomic::AtomicUsize, time::Duration};
+
+use crate::{
+    core::{
+        app::App,
+        context::{Context, Ref},
+    },
+    handler::UpdateHandler,
+    types::{Text, Update},
+};
+
+use super::*;
+
+fn create_update(text: &str) -> Update {
+    serde_json::from_value(serde_json::json!({
+        "update_id": 1,
+        "message": {
+            "message_id": 1111,
+            "date": 0,
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "chat": {"id": 1, "type": "private", "first_name": "test"},
+            "text": text
+        }
+    }))
+    .unwrap()
+}
+
+#[derive(Clone, Default)]
+struct Counter {
+    value: Arc<AtomicUsize>,
+}
+
+/// Sleeps for a number of milliseconds given in the text of a message.
+async fn handler(counter: Ref<Counter>, text: Text) {
+    let delay = text.data.parse().unwrap();
+    tokio::time::sleep(Duration::from_millis(delay)).await;
+    counter.value.fetch_add(1, Ordering::SeqCst);
+}
+
+async fn run_slow_and_fast(limit: ConcurrencyLimit, slow: &str) -> Counter {
+    let counter = Counter::default();
+    let mut context = Context::default();
+    context.insert(counter.clone());
+    let app = App::new(context, handler).with_concurrency_limit(limit);
+    let slow = tokio::spawn({
+        let app = app.clone();
+        let update = create_update(slow);
+        async move { app.handle(update).await }
+    });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    app.handle(create_update("0")).await;
+    slow.await.unwrap();
+    counter
+}
+
+#[tokio::test]
+async fn wait() {
+    let limit = ConcurrencyLimit::wait(1);
+    let counter = run_slow_and_fast(limit.clone(), "50").await;
+    assert_eq!(counter.value.load(Ordering::SeqCst), 2);
+    assert_eq!(
+        limit.get_counters(),
+        ConcurrencyCounters {
+            waited: 1,
+            dropped: 0,
+            rejected: 0
+        }
+    );
+}
+
+#[tokio::test]
+async fn drop_oldest() {
+    let limit = ConcurrencyLimit::drop_oldest(1);
+    let counter = run_slow_and_fast(limit.clone(), "60000").await;
+    assert_eq!(counter.value.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        limit.get_counters(),
+        ConcurrencyCounters {
+            waited: 0,
+            dropped: 1,
+            rejected: 0
+        }
+    );
+}
+
+#[tokio::test]
+async fn reject() {
+    let limit = ConcurrencyLimit::reject(1);
+    let counter = run_slow_and_fast(limit.clone(), "50").await;
+    assert_eq!(counter.value.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        limit.get_counters(),
+        ConcurrencyCounters {
+            waited: 0,
+            dropped: 0,
+            rejected: 1
+        }
+    );
+}
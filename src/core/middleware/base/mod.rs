@@ -0,0 +1,146 @@
+use std::{future::Future, sync::Arc};
+
+use futures_util::future::BoxFuture;
+
+use crate::core::{
+    cache::with_update_cache,
+    handler::{HandlerInput, HandlerResult, IntoHandlerResult},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Allows to run a code before and after a handler.
+///
+/// Unlike a [`crate::Predicate`], a middleware has access to the result of the handler
+/// and may change both the input and the result.
+///
+/// A middleware can be attached to an [`crate::App`], a [`crate::Chain`]
+/// or a single handler using [`crate::MiddlewareExt`].
+pub trait Middleware: Send + Sync + 'static {
+    /// Handles an input.
+    ///
+    /// Call [`Next::run`] to pass the input to the next middleware or the handler.
+    /// It is possible not to call it in order to skip the handler.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input for the handler.
+    /// * `next` - The rest of the processing pipeline.
+    fn handle(&self, input: HandlerInput, next: Next) -> impl Future<Output = HandlerResult> + Send;
+}
+
+impl<F, R> Middleware for F
+where
+    F: Fn(HandlerInput, Next) -> R + Send + Sync + 'static,
+    R: Future + Send,
+    R::Output: IntoHandlerResult,
+{
+    async fn handle(&self, input: HandlerInput, next: Next) -> HandlerResult {
+        (self)(input, next).await.into_result()
+    }
+}
+
+/// Represents the rest of the processing pipeline for a [`Middleware`].
+pub struct Next {
+    inner: BoxedRun,
+    input: HandlerInput,
+}
+
+impl Next {
+    pub(in crate::core) fn new<F>(input: &HandlerInput, inner: F) -> Self
+    where
+        F: FnOnce(HandlerInput) -> BoxFuture<'static, HandlerResult> + Send + 'static,
+    {
+        Self {
+            inner: Box::new(inner),
+            input: input.clone(),
+        }
+    }
+
+    /// Runs the next middleware or the handler.
+    ///
+    /// When the input differs from the one passed to the middleware,
+    /// inputs extracted for the original update are not reused, see [`crate::Cached`].
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input to pass.
+    pub async fn run(self, input: HandlerInput) -> HandlerResult {
+        let is_changed = input.update != self.input.update || !Arc::ptr_eq(&input.context, &self.input.context);
+        if is_changed {
+            with_update_cache((self.inner)(input)).await
+        } else {
+            (self.inner)(input).await
+        }
+    }
+}
+
+trait BoxedMiddleware: Send + Sync {
+    fn handle(&self, input: HandlerInput, next: Next) -> BoxFuture<'_, HandlerResult>;
+}
+
+impl<M> BoxedMiddleware for M
+where
+    M: Middleware,
+{
+    fn handle(&self, input: HandlerInput, next: Next) -> BoxFuture<'_, HandlerResult> {
+        Box::pin(Middleware::handle(self, input, next))
+    }
+}
+
+/// A list of middlewares to run around a handler.
+#[derive(Clone, Default)]
+pub(in crate::core) struct MiddlewareStack {
+    items: Arc<Vec<Arc<dyn BoxedMiddleware>>>,
+}
+
+impl MiddlewareStack {
+    /// Adds a middleware to the end of the stack.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the stack is shared.
+    pub(in crate::core) fn push<M>(&mut self, middleware: M)
+    where
+        M: Middleware,
+    {
+        Arc::get_mut(&mut self.items)
+            .expect("Can not add middleware, stack is shared")
+            .push(Arc::new(middleware));
+    }
+
+    pub(in crate::core) fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Runs middlewares in the order they are added, the handler runs last.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input for the first middleware.
+    /// * `handler` - The handler to run after all middlewares.
+    pub(in crate::core) fn run<F>(&self, input: HandlerInput, handler: F) -> BoxFuture<'static, HandlerResult>
+    where
+        F: FnOnce(HandlerInput) -> BoxFuture<'static, HandlerResult> + Send + 'static,
+    {
+        run_from(self.items.clone(), 0, input, Box::new(handler))
+    }
+}
+
+type BoxedRun = Box<dyn FnOnce(HandlerInput) -> BoxFuture<'static, HandlerResult> + Send>;
+
+fn run_from(
+    items: Arc<Vec<Arc<dyn BoxedMiddleware>>>,
+    idx: usize,
+    input: HandlerInput,
+    handler: BoxedRun,
+) -> BoxFuture<'static, HandlerResult> {
+    match items.get(idx).cloned() {
+        Some(middleware) => Box::pin(async move {
+            let next = Next::new(&input, move |input| run_from(items, idx + 1, input, handler));
+            middleware.handle(input, next).await
+        }),
+        None => handler(input),
+    }
+}
@@ -0,0 +1,169 @@
+use std::{
+    error::Error,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    core::{
+        app::App,
+        chain::{Chain, ChainResult},
+        context::Context,
+        handler::Handler,
+        middleware::ext::MiddlewareExt,
+    },
+    handler::UpdateHandler,
+    types::{CallbackQuery, Update},
+};
+
+use super::*;
+
+#[derive(Clone, Default)]
+struct Trace {
+    steps: Arc<Mutex<Vec<String>>>,
+}
+
+#[derive(Debug)]
+struct ExampleError;
+
+impl fmt::Display for ExampleError {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        write!(out, "Example error")
+    }
+}
+
+impl Error for ExampleError {}
+
+fn create_middleware(name: &'static str) -> impl Middleware {
+    move |input: HandlerInput, next: Next| async move {
+        let trace = input.context.get::<Trace>().unwrap().clone();
+        trace.steps.lock().unwrap().push(format!("{name}:before"));
+        let result = next.run(input).await;
+        let step = format!("{name}:after:{}", result.is_ok());
+        trace.steps.lock().unwrap().push(step);
+        result
+    }
+}
+
+struct SkipMiddleware;
+
+impl Middleware for SkipMiddleware {
+    async fn handle(&self, input: HandlerInput, _next: Next) -> HandlerResult {
+        let trace = input.context.get::<Trace>().unwrap();
+        trace.steps.lock().unwrap().push("skip".into());
+        Ok(())
+    }
+}
+
+async fn handler(input: HandlerInput) -> Result<(), ExampleError> {
+    let trace = input.context.get::<Trace>().unwrap();
+    trace.steps.lock().unwrap().push("handler".into());
+    if input.update.id == 1 {
+        Ok(())
+    } else {
+        Err(ExampleError)
+    }
+}
+
+fn create_update(update_id: i64) -> Update {
+    serde_json::from_value(serde_json::json!({
+        "update_id": update_id,
+        "message": {
+            "message_id": 1111,
+            "date": 0,
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "chat": {"id": 1, "type": "private", "first_name": "test"},
+            "text": "test"
+        }
+    }))
+    .unwrap()
+}
+
+fn create_input(update_id: i64) -> (HandlerInput, Trace) {
+    let trace = Trace::default();
+    let mut context = Context::default();
+    context.insert(trace.clone());
+    let input = HandlerInput {
+        update: create_update(update_id),
+        context: Arc::new(context),
+        extensions: Default::default(),
+    };
+    (input, trace)
+}
+
+#[tokio::test]
+async fn app() {
+    let trace = Trace::default();
+    let mut context = Context::default();
+    context.insert(trace.clone());
+    let app = App::new(context, handler)
+        .with_middleware(create_middleware("first"))
+        .with_middleware(create_middleware("second"));
+    app.handle(create_update(1)).await;
+    assert_eq!(
+        *trace.steps.lock().unwrap(),
+        vec![
+            "first:before",
+            "second:before",
+            "handler",
+            "second:after:true",
+            "first:after:true"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn chain() {
+    let chain = Chain::once().with(handler).with_middleware(create_middleware("chain"));
+
+    let (input, trace) = create_input(1);
+    assert!(chain.handle(input).await.into_result().is_ok());
+    assert_eq!(
+        *trace.steps.lock().unwrap(),
+        vec!["chain:before", "handler", "chain:after:true"]
+    );
+
+    let (input, trace) = create_input(2);
+    assert!(chain.handle(input).await.into_result().is_err());
+    assert_eq!(
+        *trace.steps.lock().unwrap(),
+        vec!["chain:before", "handler", "chain:after:false"]
+    );
+}
+
+#[tokio::test]
+async fn decorator() {
+    let chain = Chain::all()
+        .with(handler.with_middleware(create_middleware("handler")))
+        .with(handler.with_middleware(SkipMiddleware));
+
+    let (input, trace) = create_input(1);
+    assert!(chain.handle(input).await.into_result().is_ok());
+    assert_eq!(
+        *trace.steps.lock().unwrap(),
+        vec!["handler:before", "handler", "handler:after:true", "skip"]
+    );
+}
+
+async fn callback_handler(input: HandlerInput, _: CallbackQuery) {
+    let trace = input.context.get::<Trace>().unwrap();
+    trace.steps.lock().unwrap().push("callback".into());
+}
+
+#[tokio::test]
+async fn decorator_skipped() {
+    let chain = Chain::once()
+        .with(callback_handler.with_middleware(create_middleware("callback")))
+        .with(handler);
+
+    let (input, trace) = create_input(1);
+    assert!(matches!(chain.handle(input).await, ChainResult::Done(Ok(()))));
+    assert_eq!(
+        *trace.steps.lock().unwrap(),
+        vec!["callback:before", "callback:after:true", "handler"]
+    );
+
+    let (input, _) = create_input(1);
+    let decorator = callback_handler.with_middleware(create_middleware("callback"));
+    assert!(matches!(decorator.handle(input).await, ChainResult::Skipped));
+}
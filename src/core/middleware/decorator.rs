@@ -0,0 +1,114 @@
+use std::{
+    collections::HashSet,
+    marker::PhantomData,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use crate::{
+    core::{
+        chain::ChainResult,
+        convert::TryFromInput,
+        describe::HandlerDescription,
+        handler::{Handler, HandlerError, HandlerInput, IntoHandlerResult},
+        middleware::base::{Middleware, Next},
+    },
+    types::AllowedUpdate,
+};
+
+/// Runs a [`Middleware`] around a single handler.
+///
+/// The middleware runs for every input,
+/// even if the input can not be converted into the input of the handler.
+/// In this case the decorator is skipped like a handler with a false predicate,
+/// so a [`crate::Chain`] continues with the next handler.
+pub struct MiddlewareDecorator<M, H, HI> {
+    middleware: Arc<M>,
+    handler: H,
+    handler_input: PhantomData<HI>,
+}
+
+impl<M, H, HI> MiddlewareDecorator<M, H, HI> {
+    /// Creates a new `MiddlewareDecorator`.
+    ///
+    /// # Arguments
+    ///
+    /// * `middleware` - A middleware to run.
+    /// * `handler` - A handler to be decorated.
+    pub fn new(middleware: M, handler: H) -> Self {
+        Self {
+            middleware: Arc::new(middleware),
+            handler,
+            handler_input: PhantomData,
+        }
+    }
+}
+
+impl<M, H, HI> Clone for MiddlewareDecorator<M, H, HI>
+where
+    H: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            middleware: self.middleware.clone(),
+            handler: self.handler.clone(),
+            handler_input: PhantomData,
+        }
+    }
+}
+
+impl<M, H, HI> Handler<HandlerInput> for MiddlewareDecorator<M, H, HI>
+where
+    M: Middleware,
+    H: Handler<HI> + Sync + 'static,
+    H::Output: Into<ChainResult>,
+    HI: TryFromInput + Sync + 'static,
+    HI::Error: 'static,
+{
+    type Output = ChainResult;
+
+    async fn handle(&self, input: HandlerInput) -> Self::Output {
+        let handler = self.handler.clone();
+        let is_skipped = Arc::new(AtomicBool::new(false));
+        let is_stopped = Arc::new(AtomicBool::new(false));
+        let next = Next::new(&input, {
+            let is_skipped = is_skipped.clone();
+            let is_stopped = is_stopped.clone();
+            move |input| {
+                Box::pin(async move {
+                    match HI::try_from_input(input).await {
+                        Ok(Some(input)) => {
+                            let result = handler.handle(input).await.into();
+                            match result {
+                                ChainResult::Skipped => is_skipped.store(true, Ordering::SeqCst),
+                                ChainResult::Stop => is_stopped.store(true, Ordering::SeqCst),
+                                _ => {}
+                            }
+                            result.into_result()
+                        }
+                        Ok(None) => {
+                            is_skipped.store(true, Ordering::SeqCst);
+                            Ok(())
+                        }
+                        Err(err) => Err(HandlerError::convert(err)),
+                    }
+                })
+            }
+        });
+        match self.middleware.handle(input, next).await {
+            Ok(()) if is_skipped.load(Ordering::SeqCst) => ChainResult::Skipped,
+            Ok(()) if is_stopped.load(Ordering::SeqCst) => ChainResult::Stop,
+            result => ChainResult::Done(result),
+        }
+    }
+
+    fn allowed_updates(&self) -> HashSet<AllowedUpdate> {
+        self.handler.allowed_updates()
+    }
+
+    fn describe(&self) -> HandlerDescription {
+        self.handler.describe()
+    }
+}
@@ -0,0 +1,22 @@
+use crate::core::{convert::TryFromInput, handler::Handler, middleware::decorator::MiddlewareDecorator};
+
+/// Provides a shortcut for wrapping a [`Handler`] by a [`MiddlewareDecorator`].
+pub trait MiddlewareExt<M, HI>: Sized {
+    /// Shortcut to wrap a [`Handler`] with a [`MiddlewareDecorator`].
+    ///
+    /// Example: `handler.with_middleware(middleware)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `middleware` - A [`crate::Middleware`] to run around the handler.
+    fn with_middleware(self, middleware: M) -> MiddlewareDecorator<M, Self, HI> {
+        MiddlewareDecorator::new(middleware, self)
+    }
+}
+
+impl<M, H, HI> MiddlewareExt<M, HI> for H
+where
+    H: Handler<HI>,
+    HI: TryFromInput,
+{
+}
@@ -0,0 +1,5 @@
+mod base;
+mod decorator;
+mod ext;
+
+pub use self::{base::*, decorator::*, ext::*};
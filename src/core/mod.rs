@@ -1,9 +1,24 @@
 mod app;
+mod cache;
 mod chain;
 mod context;
 mod convert;
+mod dedup;
+mod describe;
+mod dispatch;
 mod error;
+mod extensions;
 mod handler;
+mod hook;
+mod kind;
+mod limit;
+mod middleware;
 mod predicate;
+mod router;
+mod shutdown;
+mod sink;
 
-pub use self::{app::*, chain::*, context::*, convert::*, error::*, handler::*, predicate::*};
+pub use self::{
+    app::*, cache::*, chain::*, context::*, convert::*, dedup::*, describe::*, dispatch::*, error::*, extensions::*,
+    handler::*, hook::*, kind::*, limit::*, middleware::*, predicate::*, router::*, shutdown::*, sink::*,
+};
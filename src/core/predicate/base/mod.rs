@@ -1,9 +1,14 @@
-use std::marker::PhantomData;
+use std::{collections::HashSet, marker::PhantomData};
 
-use crate::core::{
-    convert::TryFromInput,
-    handler::{Handler, HandlerError, HandlerResult, IntoHandlerResult},
-    predicate::result::PredicateResult,
+use crate::{
+    core::{
+        chain::ChainResult,
+        convert::TryFromInput,
+        describe::HandlerDescription,
+        handler::{Handler, HandlerError, HandlerResult, IntoHandlerResult},
+        predicate::result::PredicateResult,
+    },
+    types::AllowedUpdate,
 };
 
 #[cfg(test)]
@@ -12,6 +17,9 @@ mod tests;
 /// Decorates a handler with a predicate, allowing control over whether the handler should run.
 ///
 /// The predicate must return a [`PredicateResult`].
+///
+/// When the handler returns [`std::ops::ControlFlow::Break`],
+/// the output is [`PredicateOutput::Stop`], so subsequent handlers of a [`crate::Chain`] will not run.
 pub struct Predicate<P, PI, H, HI> {
     predicate: P,
     predicate_input: PhantomData<PI>,
@@ -43,7 +51,7 @@ where
     PI: TryFromInput + Sync + 'static,
     PI::Error: 'static,
     H: Handler<HI> + Sync + 'static,
-    H::Output: IntoHandlerResult,
+    H::Output: Into<ChainResult>,
     HI: TryFromInput + Sync + 'static,
     HI::Error: 'static,
 {
@@ -51,11 +59,41 @@ where
 
     async fn handle(&self, (predicate_input, handler_input): (PI, HI)) -> Self::Output {
         let predicate_result = self.predicate.handle(predicate_input).await.into();
+        #[cfg(feature = "tracing")]
+        record_decision::<P>(&predicate_result);
         match predicate_result {
-            PredicateResult::True => self.handler.handle(handler_input).await.into_result().into(),
+            PredicateResult::True => self.handler.handle(handler_input).await.into().into(),
             _ => predicate_result.into(),
         }
     }
+
+    fn allowed_updates(&self) -> HashSet<AllowedUpdate> {
+        let kinds = self.handler.allowed_updates();
+        let mut result = self.predicate.allowed_updates();
+        result.retain(|x| kinds.contains(x));
+        result
+    }
+
+    fn describe(&self) -> HandlerDescription {
+        let predicate = self.predicate.describe();
+        self.handler.describe().with_predicate(predicate.get_name().to_string())
+    }
+}
+
+/// Records a predicate decision on the current span.
+#[cfg(feature = "tracing")]
+pub(in crate::core) fn record_decision<P>(result: &PredicateResult) {
+    let predicate = std::any::type_name::<P>();
+    let decision = match result {
+        PredicateResult::True => "true",
+        PredicateResult::False => "false",
+        PredicateResult::Stop => "stop",
+        PredicateResult::Err(_) => "error",
+    };
+    let span = tracing::Span::current();
+    span.record("predicate", predicate);
+    span.record("decision", decision);
+    tracing::debug!(predicate, decision, "Predicate decision");
 }
 
 impl<P, PI, H, HI> Clone for Predicate<P, PI, H, HI>
@@ -79,6 +117,8 @@ pub enum PredicateOutput {
     True(HandlerResult),
     /// A decorated handler has not been executed.
     False,
+    /// A decorated handler has not been executed and the update is considered handled.
+    Stop,
     /// An error occurred during a predicate execution.
     Err(HandlerError),
 }
@@ -88,6 +128,7 @@ impl From<PredicateResult> for PredicateOutput {
         match result {
             PredicateResult::True => PredicateOutput::True(Ok(())),
             PredicateResult::False => PredicateOutput::False,
+            PredicateResult::Stop => PredicateOutput::Stop,
             PredicateResult::Err(err) => PredicateOutput::Err(err),
         }
     }
@@ -99,11 +140,22 @@ impl From<HandlerResult> for PredicateOutput {
     }
 }
 
+impl From<ChainResult> for PredicateOutput {
+    fn from(result: ChainResult) -> Self {
+        match result {
+            ChainResult::Done(result) => PredicateOutput::True(result),
+            ChainResult::Err(err) => PredicateOutput::Err(err),
+            ChainResult::Skipped => PredicateOutput::False,
+            ChainResult::Stop => PredicateOutput::Stop,
+        }
+    }
+}
+
 impl IntoHandlerResult for PredicateOutput {
     fn into_result(self) -> HandlerResult {
         match self {
             PredicateOutput::True(result) => result,
-            PredicateOutput::False => Ok(()),
+            PredicateOutput::False | PredicateOutput::Stop => Ok(()),
             PredicateOutput::Err(err) => Err(err),
         }
     }
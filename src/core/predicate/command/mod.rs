@@ -1,8 +1,46 @@
-use crate::{core::handler::Handler, types::Command};
+use std::{
+    future::Future,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use crate::{
+    core::{describe::HandlerDescription, handler::Handler},
+    types::Command,
+};
 
 #[cfg(test)]
 mod tests;
 
+tokio::task_local! {
+    static COMMAND_MATCH: Arc<AtomicBool>;
+}
+
+/// Runs a future and returns whether a command has been matched in it.
+///
+/// A command is matched by a [`CommandPredicate`] or a [`crate::CommandRouter`] route.
+/// The match is also recorded in an outer scope, so nested chains are tracked as well.
+/// Matches recorded in other tasks are not tracked.
+pub(in crate::core) async fn track_command_match<F>(future: F) -> (F::Output, bool)
+where
+    F: Future,
+{
+    let is_matched = Arc::new(AtomicBool::new(false));
+    let output = COMMAND_MATCH.scope(is_matched.clone(), future).await;
+    let is_matched = is_matched.load(Ordering::SeqCst);
+    if is_matched {
+        record_command_match();
+    }
+    (output, is_matched)
+}
+
+/// Records a matched command for the current [`track_command_match`] scope.
+pub(in crate::core) fn record_command_match() {
+    let _ = COMMAND_MATCH.try_with(|is_matched| is_matched.store(true, Ordering::SeqCst));
+}
+
 /// Allows to run a handler only for a specific command.
 #[derive(Clone)]
 pub struct CommandPredicate {
@@ -24,6 +62,14 @@ impl Handler<Command> for CommandPredicate {
     type Output = bool;
 
     async fn handle(&self, input: Command) -> Self::Output {
-        input.get_name() == self.name
+        let is_matched = input.get_name() == self.name;
+        if is_matched {
+            record_command_match();
+        }
+        is_matched
+    }
+
+    fn describe(&self) -> HandlerDescription {
+        HandlerDescription::new(format!("command {}", self.name))
     }
 }
@@ -1,4 +1,4 @@
-use std::error::Error;
+use std::{error::Error, ops::ControlFlow};
 
 use crate::core::handler::HandlerError;
 
@@ -12,6 +12,10 @@ pub enum PredicateResult {
     True,
     /// A decorated handler was not executed.
     False,
+    /// A decorated handler was not executed and subsequent handlers in a [`crate::Chain`] must not run.
+    ///
+    /// The update is considered handled.
+    Stop,
     /// An error occurred during the predicate execution.
     Err(HandlerError),
 }
@@ -26,6 +30,15 @@ impl From<bool> for PredicateResult {
     }
 }
 
+impl From<ControlFlow<()>> for PredicateResult {
+    fn from(value: ControlFlow<()>) -> Self {
+        match value {
+            ControlFlow::Continue(()) => PredicateResult::True,
+            ControlFlow::Break(()) => PredicateResult::Stop,
+        }
+    }
+}
+
 impl<T, E> From<Result<T, E>> for PredicateResult
 where
     T: Into<PredicateResult>,
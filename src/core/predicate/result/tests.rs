@@ -23,4 +23,10 @@ fn convert_result() {
         Err::<bool, ExampleError>(ExampleError).into(),
         PredicateResult::Err(_)
     ));
+    assert!(matches!(ControlFlow::Continue(()).into(), PredicateResult::True));
+    assert!(matches!(ControlFlow::Break(()).into(), PredicateResult::Stop));
+    assert!(matches!(
+        Ok::<ControlFlow<()>, ExampleError>(ControlFlow::Break(())).into(),
+        PredicateResult::Stop
+    ));
 }
@@ -0,0 +1,110 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use crate::core::{convert::TryFromInput, handler::HandlerInput};
+
+/// Segments of a callback data matched by a [`crate::CallbackRouter`] pattern.
+///
+/// Contains segments matched by `*` followed by segments after the end of the pattern.
+/// For example, `menu:*:open` pattern gives `["42", "now"]` for `menu:42:open:now` data.
+///
+/// Available only for handlers added to the [`crate::CallbackRouter`],
+/// the router passes them in [`HandlerInput::extensions`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CallbackArgs {
+    items: Vec<String>,
+}
+
+impl CallbackArgs {
+    pub(super) fn new(items: Vec<String>) -> Self {
+        Self { items }
+    }
+
+    /// Returns a segment by index.
+    ///
+    /// # Arguments
+    ///
+    /// * `idx` - The index of the segment.
+    pub fn get(&self, idx: usize) -> Option<&str> {
+        self.items.get(idx).map(String::as_str)
+    }
+
+    /// Parses a segment by index.
+    ///
+    /// # Arguments
+    ///
+    /// * `idx` - The index of the segment.
+    pub fn parse<T>(&self, idx: usize) -> Result<T, CallbackArgsError>
+    where
+        T: FromStr,
+        T::Err: Error + Send + Sync + 'static,
+    {
+        let value = self.get(idx).ok_or(CallbackArgsError::NotFound(idx))?;
+        value.parse().map_err(|err| CallbackArgsError::Parse {
+            idx,
+            source: Box::new(err),
+        })
+    }
+
+    /// Returns an iterator over segments.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.items.iter().map(String::as_str)
+    }
+
+    /// Returns the number of segments.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Indicates whether there are no segments.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl TryFromInput for CallbackArgs {
+    type Error = CallbackArgsError;
+
+    async fn try_from_input(input: HandlerInput) -> Result<Option<Self>, Self::Error> {
+        match input.extensions.get::<Self>() {
+            Some(args) => Ok(Some(args.clone())),
+            None => Err(CallbackArgsError::Unavailable),
+        }
+    }
+}
+
+/// An error when getting [`CallbackArgs`].
+#[derive(Debug)]
+pub enum CallbackArgsError {
+    /// The handler is called outside of the [`crate::CallbackRouter`].
+    Unavailable,
+    /// A segment with the index is not found.
+    NotFound(usize),
+    /// Could not parse a segment.
+    Parse {
+        /// The index of the segment.
+        idx: usize,
+        /// The actual error.
+        source: Box<dyn Error + Send + Sync>,
+    },
+}
+
+impl Error for CallbackArgsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use self::CallbackArgsError::*;
+        match self {
+            Unavailable | NotFound(_) => None,
+            Parse { source, .. } => Some(source.as_ref()),
+        }
+    }
+}
+
+impl fmt::Display for CallbackArgsError {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        use self::CallbackArgsError::*;
+        match self {
+            Unavailable => write!(out, "Callback arguments are available only in CallbackRouter handlers"),
+            NotFound(idx) => write!(out, "Callback argument {idx} not found"),
+            Parse { idx, source } => write!(out, "Could not parse callback argument {idx}: {source}"),
+        }
+    }
+}
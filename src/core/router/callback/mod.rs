@@ -0,0 +1,223 @@
+use std::{collections::HashSet, error::Error, fmt, sync::Arc};
+
+use crate::{
+    api::{Client, ExecuteError},
+    core::{
+        chain::{ChainHandler, ChainResult, ConvertHandler},
+        convert::TryFromInput,
+        describe::HandlerDescription,
+        handler::{Handler, HandlerError, HandlerInput},
+        router::run_route,
+    },
+    types::{AllowedUpdate, AnswerCallbackQuery, CallbackQuery},
+};
+
+pub use self::args::{CallbackArgs, CallbackArgsError};
+
+mod args;
+
+#[cfg(test)]
+mod tests;
+
+const SEPARATOR: char = ':';
+const WILDCARD: &str = "*";
+
+type AnswerFactory = dyn Fn(&CallbackQuery) -> AnswerCallbackQuery + Send + Sync;
+
+/// Dispatches callback queries to handlers by a pattern of the callback data.
+///
+/// A pattern consists of segments separated by `:`.
+/// A literal segment must be equal to the segment of the data, `*` matches any segment.
+/// The pattern matches a prefix of the data, e.g. `menu:settings` matches `menu:settings:language`.
+///
+/// Segments matched by `*` and the rest of the data are available
+/// in the handler through the [`CallbackArgs`] extractor.
+///
+/// Patterns are checked in the order they were added, the first matched handler runs.
+/// The router is skipped when the update doesn't contain a callback query
+/// or when the data doesn't match and [`Self::with_unmatched_answer`] is not set.
+#[derive(Clone, Default)]
+pub struct CallbackRouter {
+    routes: Arc<Vec<Route>>,
+    unmatched_answer: Option<Arc<AnswerFactory>>,
+}
+
+struct Route {
+    pattern: String,
+    segments: Vec<Segment>,
+    handler: Arc<dyn ChainHandler + Sync>,
+}
+
+enum Segment {
+    Literal(String),
+    Wildcard,
+}
+
+impl Route {
+    fn new(pattern: String, handler: Arc<dyn ChainHandler + Sync>) -> Self {
+        let segments = pattern
+            .split(SEPARATOR)
+            .map(|segment| match segment {
+                WILDCARD => Segment::Wildcard,
+                segment => Segment::Literal(String::from(segment)),
+            })
+            .collect();
+        Self {
+            pattern,
+            segments,
+            handler,
+        }
+    }
+
+    fn matches(&self, data: &str) -> Option<CallbackArgs> {
+        let mut items = Vec::new();
+        let mut parts = data.split(SEPARATOR);
+        for segment in &self.segments {
+            let part = parts.next()?;
+            match segment {
+                Segment::Literal(value) if value != part => return None,
+                Segment::Literal(_) => {}
+                Segment::Wildcard => items.push(String::from(part)),
+            }
+        }
+        items.extend(parts.map(String::from));
+        Some(CallbackArgs::new(items))
+    }
+}
+
+impl CallbackRouter {
+    /// Creates a new `CallbackRouter`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a handler for a pattern of the callback data.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The pattern, e.g. `menu:settings:*`.
+    /// * `handler` - The handler to add.
+    ///
+    /// # Panics
+    ///
+    /// Panics when trying to add a route to a shared router.
+    pub fn with_route<P, H, I, O>(mut self, pattern: P, handler: H) -> Self
+    where
+        P: Into<String>,
+        H: Handler<I, Output = O> + Sync + Clone + 'static,
+        I: TryFromInput + Sync + 'static,
+        O: Into<ChainResult>,
+    {
+        let routes = Arc::get_mut(&mut self.routes).expect("Can not add route, router is shared");
+        routes.push(Route::new(pattern.into(), ConvertHandler::shared(handler, None, true)));
+        self
+    }
+
+    /// Sets an answer for queries which don't match any pattern.
+    ///
+    /// The answer requires a [`Client`] in the [`crate::Context`].
+    ///
+    /// # Arguments
+    ///
+    /// * `factory` - A function that creates the answer for the query.
+    pub fn with_unmatched_answer<F>(mut self, factory: F) -> Self
+    where
+        F: Fn(&CallbackQuery) -> AnswerCallbackQuery + Send + Sync + 'static,
+    {
+        self.unmatched_answer = Some(Arc::new(factory));
+        self
+    }
+
+    fn find(&self, data: &str) -> Option<(&Route, CallbackArgs)> {
+        self.routes
+            .iter()
+            .find_map(|route| route.matches(data).map(|args| (route, args)))
+    }
+
+    async fn answer_unmatched(&self, query: &CallbackQuery, input: &HandlerInput) -> ChainResult {
+        let factory = match self.unmatched_answer {
+            Some(ref factory) => factory,
+            None => {
+                log::debug!("Handler for callback data {:?} is not found", query.data);
+                return ChainResult::Skipped;
+            }
+        };
+        let client = match input.context.get::<Client>() {
+            Some(client) => client,
+            None => return ChainResult::Err(HandlerError::new(CallbackRouterError::ClientNotFound)),
+        };
+        let result = client
+            .execute(factory(query))
+            .await
+            .map(|_| ())
+            .map_err(|err| HandlerError::new(CallbackRouterError::Answer(err)));
+        ChainResult::Done(result)
+    }
+}
+
+impl Handler<HandlerInput> for CallbackRouter {
+    type Output = ChainResult;
+
+    async fn handle(&self, input: HandlerInput) -> Self::Output {
+        let Ok(query) = CallbackQuery::try_from_input(input.clone()).await;
+        let query = match query {
+            Some(query) => query,
+            None => return ChainResult::Skipped,
+        };
+        match query.data.as_deref().and_then(|data| self.find(data)) {
+            Some((route, args)) => {
+                let mut input = input;
+                input.extensions.insert(args);
+                run_route(route.handler.as_ref(), input).await
+            }
+            None => self.answer_unmatched(&query, &input).await,
+        }
+    }
+
+    fn allowed_updates(&self) -> HashSet<AllowedUpdate> {
+        CallbackQuery::allowed_updates()
+    }
+
+    fn describe(&self) -> HandlerDescription {
+        let handlers = self
+            .routes
+            .iter()
+            .map(|route| {
+                route
+                    .handler
+                    .describe()
+                    .with_predicate(format!("callback data {}", route.pattern))
+            })
+            .collect();
+        HandlerDescription::group("CallbackRouter", "by callback data", handlers)
+    }
+}
+
+/// An error when answering an unmatched callback query.
+#[derive(Debug)]
+pub enum CallbackRouterError {
+    /// [`Client`] is not found in the [`crate::Context`].
+    ClientNotFound,
+    /// Could not answer the query.
+    Answer(ExecuteError),
+}
+
+impl Error for CallbackRouterError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use self::CallbackRouterError::*;
+        match self {
+            ClientNotFound => None,
+            Answer(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for CallbackRouterError {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        use self::CallbackRouterError::*;
+        match self {
+            ClientNotFound => write!(out, "Could not answer callback query: client not found in context"),
+            Answer(err) => write!(out, "Could not answer callback query: {err}"),
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use std::sync::Mutex;
+
+use crate::{
+    core::context::{Context, Ref},
+    types::Update,
+};
+
+use super::*;
+
+#[derive(Clone, Default)]
+struct Seen {
+    lines: Arc<Mutex<Vec<String>>>,
+}
+
+async fn settings(seen: Ref<Seen>, args: CallbackArgs) {
+    let line = format!("settings {:?}", args.iter().collect::<Vec<_>>());
+    seen.lines.lock().unwrap().push(line);
+}
+
+async fn item(seen: Ref<Seen>, args: CallbackArgs) -> Result<(), CallbackArgsError> {
+    let id: u32 = args.parse(0)?;
+    seen.lines.lock().unwrap().push(format!("item {id}"));
+    Ok(())
+}
+
+async fn menu(seen: Ref<Seen>, args: CallbackArgs) {
+    seen.lines.lock().unwrap().push(format!("menu {}", args.len()));
+}
+
+fn create_input(data: Option<&str>, context: Context) -> HandlerInput {
+    let mut query = serde_json::json!({
+        "id": "query-id",
+        "from": {"id": 1, "is_bot": false, "first_name": "test"},
+        "chat_instance": "instance"
+    });
+    if let Some(data) = data {
+        query["data"] = serde_json::json!(data);
+    }
+    let update: Update = serde_json::from_value(serde_json::json!({
+        "update_id": 1,
+        "callback_query": query
+    }))
+    .unwrap();
+    HandlerInput {
+        update,
+        context: Arc::new(context),
+        extensions: Default::default(),
+    }
+}
+
+fn create_context(seen: &Seen) -> Context {
+    let mut context = Context::default();
+    context.insert(seen.clone());
+    context
+}
+
+fn create_router() -> CallbackRouter {
+    CallbackRouter::new()
+        .with_route("menu:settings", settings)
+        .with_route("menu:item:*:open", item)
+        .with_route("menu", menu)
+}
+
+#[tokio::test]
+async fn route() {
+    let seen = Seen::default();
+    let router = create_router();
+    for (data, expected) in [
+        ("menu:settings", Some("settings []")),
+        ("menu:settings:language:en", Some("settings [\"language\", \"en\"]")),
+        ("menu:item:42:open", Some("item 42")),
+        ("menu:item:42", Some("menu 2")),
+        ("menu", Some("menu 0")),
+        ("menus", None),
+        ("other:settings", None),
+    ] {
+        let result = router.handle(create_input(Some(data), create_context(&seen))).await;
+        match expected {
+            Some(expected) => {
+                assert!(matches!(result, ChainResult::Done(Ok(()))), "{data}");
+                assert_eq!(std::mem::take(&mut *seen.lines.lock().unwrap()), vec![expected]);
+            }
+            None => {
+                assert!(matches!(result, ChainResult::Skipped), "{data}");
+                assert!(seen.lines.lock().unwrap().is_empty());
+            }
+        }
+    }
+    let result = router.handle(create_input(None, create_context(&seen))).await;
+    assert!(matches!(result, ChainResult::Skipped));
+    let update: Update = serde_json::from_value(serde_json::json!({
+        "update_id": 1,
+        "message": {
+            "message_id": 1111,
+            "date": 0,
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "chat": {"id": 1, "type": "private", "first_name": "test"},
+            "text": "menu"
+        }
+    }))
+    .unwrap();
+    assert!(matches!(router.handle(update.into()).await, ChainResult::Skipped));
+}
+
+async fn spawned(input: HandlerInput) -> Result<(), CallbackArgsError> {
+    let seen = input.context.get::<Seen>().unwrap().clone();
+    let args = tokio::spawn(CallbackArgs::try_from_input(input))
+        .await
+        .unwrap()?
+        .unwrap();
+    let line = format!("spawned {:?}", args.iter().collect::<Vec<_>>());
+    seen.lines.lock().unwrap().push(line);
+    Ok(())
+}
+
+#[tokio::test]
+async fn args_in_other_task() {
+    let seen = Seen::default();
+    let router = CallbackRouter::new().with_route("spawned:*", spawned);
+    let input = create_input(Some("spawned:1"), create_context(&seen));
+    assert!(matches!(router.handle(input).await, ChainResult::Done(Ok(()))));
+    assert_eq!(
+        std::mem::take(&mut *seen.lines.lock().unwrap()),
+        vec![r#"spawned ["1"]"#]
+    );
+}
+
+#[tokio::test]
+async fn args_error() {
+    let seen = Seen::default();
+    let router = create_router();
+    let result = router
+        .handle(create_input(Some("menu:item:x:open"), create_context(&seen)))
+        .await;
+    match result {
+        ChainResult::Done(Err(err)) => {
+            assert_eq!(err.get_handler_name(), Some(std::any::type_name_of_val(&item)));
+            assert!(err.to_string().starts_with("Could not parse callback argument 0"));
+        }
+        _ => panic!("Unexpected result"),
+    }
+    assert!(seen.lines.lock().unwrap().is_empty());
+
+    let args = CallbackArgs::new(vec![String::from("1")]);
+    assert_eq!(args.get(0), Some("1"));
+    assert!(!args.is_empty());
+    assert!(matches!(args.parse::<u8>(1), Err(CallbackArgsError::NotFound(1))));
+
+    let err = CallbackArgs::try_from_input(create_input(Some("menu"), Context::default()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, CallbackArgsError::Unavailable));
+}
+
+#[tokio::test]
+async fn unmatched_answer_without_client() {
+    let router = create_router().with_unmatched_answer(|query| AnswerCallbackQuery::new(&query.id));
+    let result = router.handle(create_input(Some("unknown"), Context::default())).await;
+    match result {
+        ChainResult::Err(err) => assert_eq!(
+            err.to_string(),
+            "Could not answer callback query: client not found in context"
+        ),
+        _ => panic!("Unexpected result"),
+    }
+}
+
+#[cfg(feature = "replay")]
+#[tokio::test]
+async fn unmatched_answer() {
+    use crate::replay::MockApi;
+
+    let server = MockApi::default()
+        .with_result("answerCallbackQuery", serde_json::json!(true))
+        .start()
+        .await
+        .unwrap();
+    let mut context = Context::default();
+    context.insert(server.get_client().unwrap());
+    let router = create_router().with_unmatched_answer(|query| {
+        AnswerCallbackQuery::new(&query.id)
+            .with_text("Unknown action")
+            .with_show_alert(true)
+    });
+    let result = router.handle(create_input(None, context)).await;
+    assert!(matches!(result, ChainResult::Done(Ok(()))));
+    let requests = server.get_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "answerCallbackQuery");
+    assert_eq!(
+        requests[0].get_json().unwrap(),
+        serde_json::json!({"callback_query_id": "query-id", "text": "Unknown action", "show_alert": true})
+    );
+}
+
+#[test]
+fn describe() {
+    let router = create_router();
+    assert_eq!(router.allowed_updates(), HashSet::from([AllowedUpdate::CallbackQuery]));
+    let description = router.describe();
+    assert_eq!(description.get_name(), "CallbackRouter");
+    assert_eq!(description.get_strategy(), Some("by callback data"));
+    let predicates = description
+        .get_handlers()
+        .iter()
+        .flat_map(|handler| handler.get_predicates())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        predicates,
+        vec![
+            "callback data menu:settings",
+            "callback data menu:item:*:open",
+            "callback data menu"
+        ]
+    );
+}
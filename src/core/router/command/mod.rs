@@ -0,0 +1,211 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use crate::{
+    core::{
+        chain::{ChainHandler, ChainResult, ConvertHandler},
+        convert::TryFromInput,
+        describe::HandlerDescription,
+        handler::{Handler, HandlerError, HandlerInput},
+        predicate::record_command_match,
+        router::run_route,
+    },
+    types::{AllowedUpdate, Command},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Dispatches commands to handlers by name.
+///
+/// The command is parsed once and the handler is found using a map,
+/// so it is faster than a [`crate::Chain`] with many [`crate::CommandPredicate`] handlers.
+///
+/// The router is skipped when the update doesn't contain a command
+/// or when the command is unknown and [`Self::with_default`] is not set.
+/// So it can be added to a [`crate::Chain`] like any other handler.
+#[derive(Clone, Default)]
+pub struct CommandRouter {
+    routes: Arc<Vec<Route>>,
+    names: Arc<HashMap<String, usize>>,
+    default: Option<Arc<dyn ChainHandler + Sync>>,
+    is_case_insensitive: bool,
+}
+
+struct Route {
+    names: Vec<String>,
+    handler: Arc<dyn ChainHandler + Sync>,
+}
+
+impl CommandRouter {
+    /// Creates a new `CommandRouter`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a handler for a command.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A name of the command with leading `/`.
+    /// * `handler` - The handler to add.
+    ///
+    /// # Panics
+    ///
+    /// Panics when trying to add a command to a shared router or when the command is already added.
+    pub fn with_command<N, H, I, O>(mut self, name: N, handler: H) -> Self
+    where
+        N: Into<String>,
+        H: Handler<I, Output = O> + Sync + Clone + 'static,
+        I: TryFromInput + Sync + 'static,
+        O: Into<ChainResult>,
+    {
+        let name = self.normalize(name.into());
+        let idx = self.routes.len();
+        self.insert_name(name.clone(), idx);
+        let routes = Arc::get_mut(&mut self.routes).expect("Can not add command, router is shared");
+        routes.push(Route {
+            names: vec![name],
+            handler: ConvertHandler::shared(handler, None, true),
+        });
+        self
+    }
+
+    /// Adds an alias for a command.
+    ///
+    /// # Arguments
+    ///
+    /// * `alias` - The alias with leading `/`.
+    /// * `name` - A name of the added command with leading `/`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when trying to add an alias to a shared router,
+    /// when the command is not found or when the alias is already added.
+    pub fn with_alias<A, N>(mut self, alias: A, name: N) -> Self
+    where
+        A: Into<String>,
+        N: Into<String>,
+    {
+        let alias = self.normalize(alias.into());
+        let name = self.normalize(name.into());
+        let idx = match self.names.get(&name) {
+            Some(idx) => *idx,
+            None => panic!("Can not add alias, command {name} is not found"),
+        };
+        self.insert_name(alias.clone(), idx);
+        let routes = Arc::get_mut(&mut self.routes).expect("Can not add alias, router is shared");
+        routes[idx].names.push(alias);
+        self
+    }
+
+    /// Sets a handler for unknown commands.
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - The handler to run when the command is not found.
+    pub fn with_default<H, I, O>(mut self, handler: H) -> Self
+    where
+        H: Handler<I, Output = O> + Sync + Clone + 'static,
+        I: TryFromInput + Sync + 'static,
+        O: Into<ChainResult>,
+    {
+        self.default = Some(ConvertHandler::shared(handler, None, true));
+        self
+    }
+
+    /// Sets whether names of commands are case-insensitive.
+    ///
+    /// Names are case-sensitive by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Whether to ignore case.
+    ///
+    /// # Panics
+    ///
+    /// Panics when commands are already added.
+    pub fn with_case_insensitive(mut self, value: bool) -> Self {
+        assert!(
+            self.routes.is_empty(),
+            "Can not change case sensitivity, commands are already added"
+        );
+        self.is_case_insensitive = value;
+        self
+    }
+
+    fn normalize(&self, name: String) -> String {
+        if self.is_case_insensitive {
+            name.to_lowercase()
+        } else {
+            name
+        }
+    }
+
+    fn insert_name(&mut self, name: String, idx: usize) {
+        let names = Arc::get_mut(&mut self.names).expect("Can not add command, router is shared");
+        if names.contains_key(&name) {
+            panic!("Command {name} is already added");
+        }
+        names.insert(name, idx);
+    }
+
+    fn find(&self, command: &Command) -> Option<&Arc<dyn ChainHandler + Sync>> {
+        let name = command.get_name();
+        let idx = if self.is_case_insensitive {
+            self.names.get(&name.to_lowercase())
+        } else {
+            self.names.get(name)
+        };
+        match idx {
+            Some(idx) => Some(&self.routes[*idx].handler),
+            None => self.default.as_ref(),
+        }
+    }
+}
+
+impl Handler<HandlerInput> for CommandRouter {
+    type Output = ChainResult;
+
+    async fn handle(&self, input: HandlerInput) -> Self::Output {
+        let command = match Command::try_from_input(input.clone()).await {
+            Ok(Some(command)) => command,
+            Ok(None) => return ChainResult::Skipped,
+            Err(err) => return ChainResult::Err(HandlerError::convert(err)),
+        };
+        let handler = match self.find(&command) {
+            Some(handler) => handler,
+            None => {
+                log::debug!("Handler for command {} is not found", command.get_name());
+                return ChainResult::Skipped;
+            }
+        };
+        record_command_match();
+        run_route(handler.as_ref(), input).await
+    }
+
+    fn allowed_updates(&self) -> HashSet<AllowedUpdate> {
+        Command::allowed_updates()
+    }
+
+    fn describe(&self) -> HandlerDescription {
+        let handlers = self
+            .routes
+            .iter()
+            .map(|route| {
+                route
+                    .handler
+                    .describe()
+                    .with_predicate(format!("command {}", route.names.join(" | ")))
+            })
+            .chain(
+                self.default
+                    .iter()
+                    .map(|handler| handler.describe().with_predicate("unknown command")),
+            )
+            .collect();
+        HandlerDescription::group("CommandRouter", "by command", handlers)
+    }
+}
@@ -0,0 +1,162 @@
+use std::sync::Mutex;
+
+use crate::{
+    core::{
+        chain::Chain,
+        context::{Context, Ref},
+        handler::{HandlerResult, IntoHandlerResult},
+    },
+    types::Update,
+};
+
+use super::*;
+
+#[derive(Clone, Default)]
+struct Handled {
+    names: Arc<Mutex<Vec<&'static str>>>,
+}
+
+async fn start(handled: Ref<Handled>, _: Command) {
+    handled.names.lock().unwrap().push("start");
+}
+
+async fn help(handled: Ref<Handled>) -> HandlerResult {
+    handled.names.lock().unwrap().push("help");
+    Err(HandlerError::new(std::io::Error::other("help error")))
+}
+
+async fn unknown(handled: Ref<Handled>, _: Command) {
+    handled.names.lock().unwrap().push("unknown");
+}
+
+async fn other(handled: Ref<Handled>) {
+    handled.names.lock().unwrap().push("other");
+}
+
+fn create_input(text: &str, handled: &Handled) -> HandlerInput {
+    let update: Update = serde_json::from_value(serde_json::json!({
+        "update_id": 1,
+        "message": {
+            "message_id": 1111,
+            "date": 0,
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "chat": {"id": 1, "type": "private", "first_name": "test"},
+            "text": text,
+            "entities": if text.starts_with('/') {
+                serde_json::json!([{"type": "bot_command", "offset": 0, "length": text.len()}])
+            } else {
+                serde_json::json!([])
+            }
+        }
+    }))
+    .unwrap();
+    let mut context = Context::default();
+    context.insert(handled.clone());
+    HandlerInput {
+        update,
+        context: Arc::new(context),
+        extensions: Default::default(),
+    }
+}
+
+#[tokio::test]
+async fn command_router() {
+    let handled = Handled::default();
+    let router = CommandRouter::new()
+        .with_command("/start", start)
+        .with_command("/help", help)
+        .with_alias("/begin", "/start");
+    let chain = Chain::once().with(router.clone()).with(other);
+
+    chain
+        .handle(create_input("/start", &handled))
+        .await
+        .into_result()
+        .unwrap();
+    chain
+        .handle(create_input("/begin", &handled))
+        .await
+        .into_result()
+        .unwrap();
+    assert_eq!(
+        std::mem::take(&mut *handled.names.lock().unwrap()),
+        vec!["start", "start"]
+    );
+
+    let err = chain
+        .handle(create_input("/help", &handled))
+        .await
+        .into_result()
+        .unwrap_err();
+    assert!(err.get_handler_name().unwrap().ends_with("::help"));
+    assert_eq!(std::mem::take(&mut *handled.names.lock().unwrap()), vec!["help"]);
+
+    // case-sensitive by default, unknown commands and other messages are skipped
+    chain
+        .handle(create_input("/START", &handled))
+        .await
+        .into_result()
+        .unwrap();
+    chain
+        .handle(create_input("/unknown", &handled))
+        .await
+        .into_result()
+        .unwrap();
+    chain
+        .handle(create_input("text", &handled))
+        .await
+        .into_result()
+        .unwrap();
+    assert_eq!(
+        std::mem::take(&mut *handled.names.lock().unwrap()),
+        vec!["other", "other", "other"]
+    );
+
+    let router = CommandRouter::new()
+        .with_case_insensitive(true)
+        .with_command("/Start", start)
+        .with_alias("/BEGIN", "/start")
+        .with_default(unknown);
+    let chain = Chain::once().with(router.clone()).with(other);
+    chain
+        .handle(create_input("/START", &handled))
+        .await
+        .into_result()
+        .unwrap();
+    chain
+        .handle(create_input("/begin", &handled))
+        .await
+        .into_result()
+        .unwrap();
+    chain
+        .handle(create_input("/unknown", &handled))
+        .await
+        .into_result()
+        .unwrap();
+    chain
+        .handle(create_input("text", &handled))
+        .await
+        .into_result()
+        .unwrap();
+    assert_eq!(
+        std::mem::take(&mut *handled.names.lock().unwrap()),
+        vec!["start", "start", "unknown", "other"]
+    );
+
+    let description = router.describe();
+    assert_eq!(description.get_strategy(), Some("by command"));
+    let predicates = description
+        .get_handlers()
+        .iter()
+        .flat_map(|handler| handler.get_predicates())
+        .collect::<Vec<_>>();
+    assert_eq!(predicates, vec!["command /start | /begin", "unknown command"]);
+}
+
+#[test]
+#[should_panic(expected = "Command /start is already added")]
+fn command_router_duplicate() {
+    let _ = CommandRouter::new()
+        .with_command("/start", start)
+        .with_alias("/start", "/start");
+}
@@ -0,0 +1,164 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use crate::{
+    core::{
+        chain::{ChainHandler, ChainResult, ConvertHandler},
+        convert::TryFromInput,
+        describe::HandlerDescription,
+        handler::{Handler, HandlerInput},
+        kind::{UpdateKindExt, get_update_kind_name},
+        router::run_route,
+    },
+    types::AllowedUpdate,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Dispatches updates to handlers by a kind of the update.
+///
+/// The handler is found using a map, so other handlers don't try to convert the input.
+/// For example, a handler added for [`AllowedUpdate::Message`]
+/// never receives an edited message, even if it accepts a [`crate::types::Message`].
+///
+/// The router is skipped when a handler for the kind is not found and [`Self::with_default`] is not set.
+/// So it can be added to a [`crate::Chain`] like any other handler.
+#[derive(Clone, Default)]
+pub struct UpdateKindRouter {
+    routes: Arc<Vec<Route>>,
+    kinds: Arc<HashMap<AllowedUpdate, usize>>,
+    default: Option<Arc<dyn ChainHandler + Sync>>,
+}
+
+struct Route {
+    kinds: Vec<AllowedUpdate>,
+    handler: Arc<dyn ChainHandler + Sync>,
+}
+
+impl UpdateKindRouter {
+    /// Creates a new `UpdateKindRouter`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a handler for a kind of updates.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of updates.
+    /// * `handler` - The handler to add, e.g. a [`crate::Chain`].
+    ///
+    /// # Panics
+    ///
+    /// Panics when trying to add a handler to a shared router or when the kind is already added.
+    pub fn with_kind<H, I, O>(self, kind: AllowedUpdate, handler: H) -> Self
+    where
+        H: Handler<I, Output = O> + Sync + Clone + 'static,
+        I: TryFromInput + Sync + 'static,
+        O: Into<ChainResult>,
+    {
+        self.with_kinds([kind], handler)
+    }
+
+    /// Adds a handler for several kinds of updates.
+    ///
+    /// # Arguments
+    ///
+    /// * `kinds` - The kinds of updates.
+    /// * `handler` - The handler to add, e.g. a [`crate::Chain`].
+    ///
+    /// # Panics
+    ///
+    /// Panics when trying to add a handler to a shared router or when a kind is already added.
+    pub fn with_kinds<K, H, I, O>(mut self, kinds: K, handler: H) -> Self
+    where
+        K: IntoIterator<Item = AllowedUpdate>,
+        H: Handler<I, Output = O> + Sync + Clone + 'static,
+        I: TryFromInput + Sync + 'static,
+        O: Into<ChainResult>,
+    {
+        let idx = self.routes.len();
+        let kinds: Vec<AllowedUpdate> = kinds.into_iter().collect();
+        let map = Arc::get_mut(&mut self.kinds).expect("Can not add handler, router is shared");
+        for kind in &kinds {
+            if map.insert(*kind, idx).is_some() {
+                panic!("Handler for {} is already added", get_update_kind_name(*kind));
+            }
+        }
+        let routes = Arc::get_mut(&mut self.routes).expect("Can not add handler, router is shared");
+        routes.push(Route {
+            kinds,
+            handler: ConvertHandler::shared(handler, None, true),
+        });
+        self
+    }
+
+    /// Sets a handler for kinds without a handler.
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - The handler to run when a handler for the kind is not found.
+    pub fn with_default<H, I, O>(mut self, handler: H) -> Self
+    where
+        H: Handler<I, Output = O> + Sync + Clone + 'static,
+        I: TryFromInput + Sync + 'static,
+        O: Into<ChainResult>,
+    {
+        self.default = Some(ConvertHandler::shared(handler, None, true));
+        self
+    }
+
+    fn find(&self, kind: Option<AllowedUpdate>) -> Option<&Arc<dyn ChainHandler + Sync>> {
+        match kind.and_then(|kind| self.kinds.get(&kind)) {
+            Some(idx) => Some(&self.routes[*idx].handler),
+            None => self.default.as_ref(),
+        }
+    }
+}
+
+impl Handler<HandlerInput> for UpdateKindRouter {
+    type Output = ChainResult;
+
+    async fn handle(&self, input: HandlerInput) -> Self::Output {
+        let kind = input.update.get_kind();
+        let handler = match self.find(kind) {
+            Some(handler) => handler,
+            None => {
+                log::debug!("Handler for {} is not found", input.update.get_kind_name());
+                return ChainResult::Skipped;
+            }
+        };
+        run_route(handler.as_ref(), input).await
+    }
+
+    fn allowed_updates(&self) -> HashSet<AllowedUpdate> {
+        let mut result: HashSet<AllowedUpdate> = self.kinds.keys().copied().collect();
+        if let Some(ref handler) = self.default {
+            result.extend(handler.allowed_updates());
+        }
+        result
+    }
+
+    fn describe(&self) -> HandlerDescription {
+        let handlers = self
+            .routes
+            .iter()
+            .map(|route| {
+                let kinds: Vec<&str> = route.kinds.iter().copied().map(get_update_kind_name).collect();
+                route
+                    .handler
+                    .describe()
+                    .with_predicate(format!("kind {}", kinds.join(" | ")))
+            })
+            .chain(
+                self.default
+                    .iter()
+                    .map(|handler| handler.describe().with_predicate("other kind")),
+            )
+            .collect();
+        HandlerDescription::group("UpdateKindRouter", "by update kind", handlers)
+    }
+}
@@ -0,0 +1,114 @@
+use std::sync::Mutex;
+
+use crate::{
+    core::{
+        chain::Chain,
+        context::{Context, Ref},
+        handler::IntoHandlerResult,
+    },
+    types::{Message, Update},
+};
+
+use super::*;
+
+#[derive(Clone, Default)]
+struct Calls {
+    names: Arc<Mutex<Vec<&'static str>>>,
+}
+
+async fn new_message(calls: Ref<Calls>, _: Message) {
+    calls.names.lock().unwrap().push("message");
+}
+
+async fn edited_message(calls: Ref<Calls>, _: Message) {
+    calls.names.lock().unwrap().push("edited");
+}
+
+async fn post(calls: Ref<Calls>, _: Message) {
+    calls.names.lock().unwrap().push("post");
+}
+
+async fn other(calls: Ref<Calls>) {
+    calls.names.lock().unwrap().push("other");
+}
+
+fn create_input(kind: &str, calls: &Calls) -> HandlerInput {
+    let chat = if kind.contains("channel") {
+        serde_json::json!({"id": -1, "type": "channel", "title": "test"})
+    } else {
+        serde_json::json!({"id": 1, "type": "private", "first_name": "test"})
+    };
+    let mut message = serde_json::json!({
+        "message_id": 1111,
+        "date": 0,
+        "chat": chat,
+        "text": "test"
+    });
+    if kind.starts_with("edited") {
+        message["edit_date"] = serde_json::json!(1);
+    }
+    let update: Update = serde_json::from_value(serde_json::json!({
+        "update_id": 1,
+        kind: message
+    }))
+    .unwrap();
+    let mut context = Context::default();
+    context.insert(calls.clone());
+    HandlerInput {
+        update,
+        context: Arc::new(context),
+        extensions: Default::default(),
+    }
+}
+
+#[tokio::test]
+async fn update_kind_router() {
+    let calls = Calls::default();
+    let router = UpdateKindRouter::new()
+        .with_kind(AllowedUpdate::Message, Chain::once().with(new_message))
+        .with_kinds([AllowedUpdate::ChannelPost, AllowedUpdate::EditedChannelPost], post);
+    let chain = Chain::once().with(router.clone()).with(other);
+    for kind in ["message", "edited_message", "channel_post", "edited_channel_post"] {
+        chain.handle(create_input(kind, &calls)).await.into_result().unwrap();
+    }
+    assert_eq!(
+        std::mem::take(&mut *calls.names.lock().unwrap()),
+        vec!["message", "other", "post", "post"]
+    );
+    assert_eq!(
+        router.allowed_updates(),
+        HashSet::from([
+            AllowedUpdate::Message,
+            AllowedUpdate::ChannelPost,
+            AllowedUpdate::EditedChannelPost
+        ])
+    );
+
+    let router = router.with_default(edited_message);
+    assert!(matches!(
+        router.handle(create_input("edited_message", &calls)).await,
+        ChainResult::Done(Ok(()))
+    ));
+    assert_eq!(std::mem::take(&mut *calls.names.lock().unwrap()), vec!["edited"]);
+    assert!(router.allowed_updates().contains(&AllowedUpdate::EditedMessage));
+
+    let description = router.describe();
+    assert_eq!(description.get_strategy(), Some("by update kind"));
+    let predicates = description
+        .get_handlers()
+        .iter()
+        .flat_map(|handler| handler.get_predicates())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        predicates,
+        vec!["kind message", "kind channel_post | edited_channel_post", "other kind"]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Handler for message is already added")]
+fn update_kind_router_duplicate() {
+    let _ = UpdateKindRouter::new()
+        .with_kind(AllowedUpdate::Message, other)
+        .with_kinds([AllowedUpdate::EditedMessage, AllowedUpdate::Message], other);
+}
@@ -0,0 +1,19 @@
+use crate::core::{
+    chain::{ChainHandler, ChainResult, run_handler},
+    handler::HandlerInput,
+};
+
+mod callback;
+mod command;
+mod kind;
+
+pub use self::{callback::*, command::*, kind::*};
+
+async fn run_route(handler: &(dyn ChainHandler + Sync), input: HandlerInput) -> ChainResult {
+    let (name, result) = run_handler(handler, input).await;
+    match result {
+        ChainResult::Done(result) => ChainResult::Done(result.map_err(|err| err.with_handler_name(name))),
+        ChainResult::Err(err) => ChainResult::Err(err.with_handler_name(name)),
+        result => result,
+    }
+}
@@ -0,0 +1,182 @@
+use std::{
+    collections::BTreeMap,
+    pin::pin,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use futures_util::future::{AbortHandle, AbortRegistration};
+use tokio::{sync::Notify, time::timeout};
+
+use crate::core::{context::Context, hook::Hooks};
+
+#[cfg(test)]
+mod tests;
+
+/// Allows to stop an [`crate::App`] gracefully.
+///
+/// Use [`crate::App::get_shutdown_handle`] to obtain the handle.
+#[derive(Clone)]
+pub struct ShutdownHandle {
+    tracker: Arc<InFlight>,
+    context: Arc<Context>,
+    hooks: Arc<Hooks>,
+}
+
+impl ShutdownHandle {
+    pub(in crate::core) fn new(tracker: Arc<InFlight>, context: Arc<Context>, hooks: Arc<Hooks>) -> Self {
+        Self {
+            tracker,
+            context,
+            hooks,
+        }
+    }
+
+    /// Stops the associated app.
+    ///
+    /// The app stops accepting new updates immediately, so updates received
+    /// from [`crate::handler::LongPoll`] or [`crate::handler::WebhookServer`] after this call are discarded.
+    /// Stop the polling loop or the server before calling this method in order not to lose them.
+    ///
+    /// Handlers that are still running when the deadline is reached are cancelled.
+    /// Shutdown hooks run after all cancelled handlers are dropped, see [`crate::App::on_shutdown`].
+    ///
+    /// # Arguments
+    ///
+    /// * `deadline` - Maximum time to wait for in-flight handlers.
+    pub async fn shutdown(self, deadline: Duration) -> ShutdownReport {
+        self.tracker.stop_accepting();
+        if timeout(deadline, self.tracker.wait_idle()).await.is_err() {
+            self.tracker.abort_all();
+            self.tracker.wait_idle().await;
+        }
+        let report = ShutdownReport {
+            completed: self.tracker.completed.load(Ordering::SeqCst),
+            cancelled: self.tracker.cancelled.load(Ordering::SeqCst),
+        };
+        log::info!(
+            "App has been stopped: {} update(s) completed, {} cancelled",
+            report.completed,
+            report.cancelled
+        );
+        self.hooks.run_shutdown(self.context).await;
+        report
+    }
+}
+
+/// Represents a result of [`ShutdownHandle::shutdown`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ShutdownReport {
+    /// Number of in-flight updates processed before the deadline.
+    pub completed: usize,
+    /// Number of in-flight updates cancelled after the deadline.
+    pub cancelled: usize,
+}
+
+/// Tracks updates currently processed by an app.
+pub(in crate::core) struct InFlight {
+    accepting: AtomicBool,
+    next_id: AtomicU64,
+    tasks: Mutex<BTreeMap<u64, AbortHandle>>,
+    idle: Notify,
+    completed: AtomicUsize,
+    cancelled: AtomicUsize,
+}
+
+impl Default for InFlight {
+    fn default() -> Self {
+        Self {
+            accepting: AtomicBool::new(true),
+            next_id: AtomicU64::new(0),
+            tasks: Mutex::new(BTreeMap::new()),
+            idle: Notify::new(),
+            completed: AtomicUsize::new(0),
+            cancelled: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl InFlight {
+    /// Registers a new in-flight update.
+    ///
+    /// Returns `None` when the app is shutting down.
+    pub(in crate::core) fn register(self: &Arc<Self>) -> Option<(InFlightGuard, AbortRegistration)> {
+        // The flag is checked under the lock, so an update is either rejected or tracked by the shutdown
+        let mut tasks = self.lock();
+        if !self.accepting.load(Ordering::SeqCst) {
+            return None;
+        }
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (handle, registration) = AbortHandle::new_pair();
+        tasks.insert(id, handle);
+        let guard = InFlightGuard {
+            id,
+            tracker: self.clone(),
+            is_cancelled: false,
+        };
+        Some((guard, registration))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<u64, AbortHandle>> {
+        self.tasks.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn stop_accepting(&self) {
+        let _tasks = self.lock();
+        self.accepting.store(false, Ordering::SeqCst);
+    }
+
+    fn abort_all(&self) {
+        for handle in self.lock().values() {
+            handle.abort();
+        }
+    }
+
+    async fn wait_idle(&self) {
+        loop {
+            let mut notified = pin!(self.idle.notified());
+            notified.as_mut().enable();
+            if self.lock().is_empty() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// Removes an update from the in-flight list when dropped.
+///
+/// Updates finished during a shutdown are counted for the [`ShutdownReport`].
+pub(in crate::core) struct InFlightGuard {
+    id: u64,
+    tracker: Arc<InFlight>,
+    is_cancelled: bool,
+}
+
+impl InFlightGuard {
+    /// Marks the update as cancelled.
+    pub(in crate::core) fn cancel(&mut self) {
+        self.is_cancelled = true;
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        let mut tasks = self.tracker.lock();
+        tasks.remove(&self.id);
+        if !self.tracker.accepting.load(Ordering::SeqCst) {
+            let counter = if self.is_cancelled {
+                &self.tracker.cancelled
+            } else {
+                &self.tracker.completed
+            };
+            counter.fetch_add(1, Ordering::SeqCst);
+        }
+        if tasks.is_empty() {
+            self.tracker.idle.notify_waiters();
+        }
+    }
+}
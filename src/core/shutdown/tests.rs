@@ -0,0 +1,151 @@
+use std::sync::atomic::AtomicUsize;
+
+use crate::{
+    core::{
+        app::App,
+        context::{Context, Ref},
+    },
+    handler::UpdateHandler,
+    types::Update,
+};
+
+use super::*;
+
+fn create_update() -> Update {
+    serde_json::from_value(serde_json::json!({
+        "update_id": 1,
+        "message": {
+            "message_id": 1111,
+            "date": 0,
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "chat": {"id": 1, "type": "private", "first_name": "test"},
+            "text": "test message from private chat"
+        }
+    }))
+    .unwrap()
+}
+
+#[derive(Clone, Default)]
+struct Counter {
+    value: Arc<AtomicUsize>,
+}
+
+async fn handler(delay: Ref<Duration>, counter: Ref<Counter>) {
+    tokio::time::sleep(*delay).await;
+    counter.value.fetch_add(1, Ordering::SeqCst);
+}
+
+#[tokio::test]
+async fn drain() {
+    let counter = Counter::default();
+    let mut context = Context::default();
+    context.insert(Duration::from_millis(50));
+    context.insert(counter.clone());
+    let app = App::new(context, handler);
+    let handle = app.get_shutdown_handle();
+    let task = tokio::spawn({
+        let app = app.clone();
+        async move { app.handle(create_update()).await }
+    });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+
+    let report = handle.shutdown(Duration::from_secs(5)).await;
+    assert_eq!(
+        report,
+        ShutdownReport {
+            completed: 1,
+            cancelled: 0
+        }
+    );
+    task.await.unwrap();
+    assert_eq!(counter.value.load(Ordering::SeqCst), 1);
+
+    // new updates are not accepted
+    app.handle(create_update()).await;
+    assert_eq!(counter.value.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn cancel() {
+    let counter = Counter::default();
+    let mut context = Context::default();
+    context.insert(Duration::from_secs(60));
+    context.insert(counter.clone());
+    let app = App::new(context, handler);
+    let handle = app.get_shutdown_handle();
+    let task = tokio::spawn({
+        let app = app.clone();
+        async move { app.handle(create_update()).await }
+    });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+
+    let report = handle.shutdown(Duration::from_millis(10)).await;
+    assert_eq!(
+        report,
+        ShutdownReport {
+            completed: 0,
+            cancelled: 1
+        }
+    );
+    task.await.unwrap();
+    assert_eq!(counter.value.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn idle() {
+    let app = App::new(Context::default(), handler);
+    let report = app.get_shutdown_handle().shutdown(Duration::from_secs(60)).await;
+    assert_eq!(report, ShutdownReport::default());
+}
+
+struct DropGuard(Counter);
+
+impl Drop for DropGuard {
+    fn drop(&mut self) {
+        self.0.value.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+async fn pending_handler(Ref(counter): Ref<Counter>) {
+    let _guard = DropGuard(counter);
+    std::future::pending::<()>().await;
+}
+
+#[tokio::test]
+async fn cancel_before_hooks() {
+    let counter = Counter::default();
+    let dropped_before_hook = Counter::default();
+    let mut context = Context::default();
+    context.insert(counter.clone());
+    let app = App::new(context, pending_handler).on_shutdown({
+        let dropped_before_hook = dropped_before_hook.clone();
+        move |context: Arc<Context>| {
+            let dropped_before_hook = dropped_before_hook.clone();
+            async move {
+                let dropped = context.get::<Counter>().unwrap().value.load(Ordering::SeqCst);
+                dropped_before_hook.value.store(dropped, Ordering::SeqCst);
+            }
+        }
+    });
+    let handle = app.get_shutdown_handle();
+    let tasks = (0..3)
+        .map(|_| {
+            let app = app.clone();
+            tokio::spawn(async move { app.handle(create_update()).await })
+        })
+        .collect::<Vec<_>>();
+    tokio::time::sleep(Duration::from_millis(10)).await;
+
+    let report = handle.shutdown(Duration::from_millis(10)).await;
+    assert_eq!(
+        report,
+        ShutdownReport {
+            completed: 0,
+            cancelled: 3
+        }
+    );
+    assert_eq!(dropped_before_hook.value.load(Ordering::SeqCst), 3);
+    for task in tasks {
+        task.await.unwrap();
+    }
+}
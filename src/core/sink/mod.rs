@@ -0,0 +1,97 @@
+use std::{error::Error, fmt, future::Future, sync::Arc};
+
+use futures_util::future::BoxFuture;
+
+use crate::{
+    core::{context::Context, handler::HandlerError},
+    types::Update,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// An error occurred in an [`crate::App`].
+#[derive(Debug)]
+pub enum AppError {
+    /// Could not convert an update into the input of the handler.
+    Convert(HandlerError),
+    /// The handler returned an error.
+    Handler(HandlerError),
+}
+
+impl AppError {
+    /// Creates an `AppError` depending on [`HandlerError::is_convert_error`].
+    pub(crate) fn new(err: HandlerError) -> Self {
+        if err.is_convert_error() {
+            AppError::Convert(err)
+        } else {
+            AppError::Handler(err)
+        }
+    }
+
+    /// Returns the underlying error.
+    pub fn get_source(&self) -> &HandlerError {
+        match self {
+            AppError::Convert(err) | AppError::Handler(err) => err,
+        }
+    }
+
+    /// Converts into the underlying error.
+    pub fn into_source(self) -> HandlerError {
+        match self {
+            AppError::Convert(err) | AppError::Handler(err) => err,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AppError::Convert(err) => write!(out, "Failed to convert input: {err}"),
+            AppError::Handler(err) => write!(out, "An error has occurred: {err}"),
+        }
+    }
+}
+
+impl Error for AppError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.get_source())
+    }
+}
+
+/// Receives errors occurred in an [`crate::App`].
+///
+/// Use [`crate::App::with_error_sink`] to set the sink.
+pub trait ErrorSink: Send + Sync + 'static {
+    /// Handles an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The error.
+    /// * `update` - The update caused the error.
+    /// * `context` - The context of the app.
+    fn handle(&self, error: AppError, update: Update, context: Arc<Context>) -> impl Future<Output = ()> + Send;
+}
+
+impl<F, R> ErrorSink for F
+where
+    F: Fn(AppError, Update, Arc<Context>) -> R + Send + Sync + 'static,
+    R: Future<Output = ()> + Send,
+{
+    async fn handle(&self, error: AppError, update: Update, context: Arc<Context>) {
+        (self)(error, update, context).await
+    }
+}
+
+pub(in crate::core) trait BoxedErrorSink: Send + Sync {
+    fn handle_boxed(&self, error: AppError, update: Update, context: Arc<Context>) -> BoxFuture<'_, ()>;
+}
+
+impl<S> BoxedErrorSink for S
+where
+    S: ErrorSink,
+{
+    fn handle_boxed(&self, error: AppError, update: Update, context: Arc<Context>) -> BoxFuture<'_, ()> {
+        Box::pin(ErrorSink::handle(self, error, update, context))
+    }
+}
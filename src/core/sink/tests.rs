@@ -0,0 +1,164 @@
+use std::sync::Mutex;
+
+use crate::{
+    core::{
+        app::App,
+        chain::Chain,
+        convert::TryFromInput,
+        handler::{HandlerInput, HandlerResult},
+        middleware::Next,
+    },
+    handler::UpdateHandler,
+};
+
+use super::*;
+
+#[derive(Clone, Default)]
+struct Errors {
+    messages: Arc<Mutex<Vec<String>>>,
+}
+
+#[derive(Debug)]
+struct ExampleError;
+
+impl fmt::Display for ExampleError {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        write!(out, "Example error")
+    }
+}
+
+impl Error for ExampleError {}
+
+struct Failing;
+
+impl TryFromInput for Failing {
+    type Error = ExampleError;
+
+    async fn try_from_input(_input: HandlerInput) -> Result<Option<Self>, Self::Error> {
+        Err(ExampleError)
+    }
+}
+
+async fn failing_input(_: Failing) {}
+
+async fn failing_handler(_: Update) -> Result<(), ExampleError> {
+    Err(ExampleError)
+}
+
+async fn sink(error: AppError, update: Update, context: Arc<Context>) {
+    let kind = match error {
+        AppError::Convert(_) => "convert",
+        AppError::Handler(_) => "handler",
+    };
+    let errors = context.get::<Errors>().unwrap();
+    errors
+        .messages
+        .lock()
+        .unwrap()
+        .push(format!("{kind}:{}:{}", update.id, error.get_source()));
+}
+
+async fn middleware(input: HandlerInput, next: Next) -> HandlerResult {
+    next.run(input).await
+}
+
+fn create_update() -> Update {
+    serde_json::from_value(serde_json::json!({
+        "update_id": 1,
+        "message": {
+            "message_id": 1111,
+            "date": 0,
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "chat": {"id": 1, "type": "private", "first_name": "test"},
+            "text": "test"
+        }
+    }))
+    .unwrap()
+}
+
+fn create_context() -> (Context, Errors) {
+    let errors = Errors::default();
+    let mut context = Context::default();
+    context.insert(errors.clone());
+    (context, errors)
+}
+
+#[tokio::test]
+async fn error_sink() {
+    let (context, errors) = create_context();
+    let app = App::new(context, failing_input).with_error_sink(sink);
+    app.handle(create_update()).await;
+    let (context, errors_handler) = create_context();
+    let app = App::new(context, failing_handler).with_error_sink(sink);
+    app.handle(create_update()).await;
+    assert_eq!(
+        *errors.messages.lock().unwrap(),
+        vec!["convert:1:Unable to convert HandlerInput into tuple: Example error"]
+    );
+    assert_eq!(
+        *errors_handler.messages.lock().unwrap(),
+        vec!["handler:1:Example error"]
+    );
+}
+
+#[tokio::test]
+async fn error_sink_with_middleware() {
+    let (context, errors) = create_context();
+    let app = App::new(context, failing_input)
+        .with_middleware(middleware)
+        .with_error_sink(sink);
+    app.handle(create_update()).await;
+    let (context, errors_handler) = create_context();
+    let app = App::new(context, failing_handler)
+        .with_middleware(middleware)
+        .with_error_sink(sink);
+    app.handle(create_update()).await;
+    assert_eq!(
+        *errors.messages.lock().unwrap(),
+        vec!["convert:1:Unable to convert HandlerInput into tuple: Example error"]
+    );
+    assert_eq!(
+        *errors_handler.messages.lock().unwrap(),
+        vec!["handler:1:Example error"]
+    );
+}
+
+#[tokio::test]
+async fn error_sink_with_chain() {
+    let (context, errors) = create_context();
+    let chain = Chain::once().with(failing_input);
+    let app = App::new(context, chain.clone()).with_error_sink(sink);
+    app.handle(create_update()).await;
+    let (context, errors_middleware) = create_context();
+    let app = App::new(context, chain)
+        .with_middleware(middleware)
+        .with_error_sink(sink);
+    app.handle(create_update()).await;
+    let (context, errors_handler) = create_context();
+    let app = App::new(context, Chain::once().with(failing_handler)).with_error_sink(sink);
+    app.handle(create_update()).await;
+    let expected = vec!["convert:1:Unable to convert HandlerInput into tuple: Example error"];
+    assert_eq!(*errors.messages.lock().unwrap(), expected);
+    assert_eq!(*errors_middleware.messages.lock().unwrap(), expected);
+    assert_eq!(
+        *errors_handler.messages.lock().unwrap(),
+        vec!["handler:1:Example error"]
+    );
+}
+
+#[test]
+fn app_error() {
+    let err = AppError::Convert(HandlerError::new(ExampleError));
+    assert_eq!(err.to_string(), "Failed to convert input: Example error");
+    let err = AppError::Handler(HandlerError::new(ExampleError));
+    assert_eq!(err.to_string(), "An error has occurred: Example error");
+    assert_eq!(err.into_source().to_string(), "Example error");
+    assert!(matches!(
+        AppError::new(HandlerError::convert(ExampleError)),
+        AppError::Convert(_)
+    ));
+    assert!(matches!(
+        AppError::new(HandlerError::new(ExampleError)),
+        AppError::Handler(_)
+    ));
+}
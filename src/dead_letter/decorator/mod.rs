@@ -0,0 +1,86 @@
+use std::{any::type_name, collections::HashSet, marker::PhantomData};
+
+use crate::{
+    core::{ChainResult, Handler, HandlerDescription, HandlerError, HandlerInput, TryFromInput},
+    dead_letter::{letter::DeadLetter, store::DeadLetterStore},
+    types::AllowedUpdate,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Saves updates failed in a handler to a [`DeadLetterStore`].
+///
+/// Errors are passed through, so they are still available for [`crate::App`]
+/// and other decorators (e.g. [`crate::ErrorDecorator`]).
+pub struct DeadLetterDecorator<S, H, HI> {
+    store: S,
+    handler: H,
+    handler_input: PhantomData<HI>,
+}
+
+impl<S, H, HI> DeadLetterDecorator<S, H, HI> {
+    /// Creates a new `DeadLetterDecorator`.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - A store for failed updates.
+    /// * `handler` - The handler to be decorated.
+    pub fn new(store: S, handler: H) -> Self {
+        Self {
+            store,
+            handler,
+            handler_input: PhantomData,
+        }
+    }
+}
+
+impl<S, H, HI> Clone for DeadLetterDecorator<S, H, HI>
+where
+    S: Clone,
+    H: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            store: self.store.clone(),
+            handler: self.handler.clone(),
+            handler_input: PhantomData,
+        }
+    }
+}
+
+impl<S, H, HI> Handler<HandlerInput> for DeadLetterDecorator<S, H, HI>
+where
+    S: DeadLetterStore + Clone + 'static,
+    H: Handler<HI> + Sync + 'static,
+    H::Output: Into<ChainResult>,
+    HI: TryFromInput + Sync,
+    HI::Error: 'static,
+{
+    type Output = ChainResult;
+
+    async fn handle(&self, input: HandlerInput) -> Self::Output {
+        let update = input.update.clone();
+        let result = match HI::try_from_input(input).await {
+            Ok(Some(input)) => self.handler.handle(input).await.into(),
+            Ok(None) => return ChainResult::Skipped,
+            Err(err) => ChainResult::Err(HandlerError::convert(err)),
+        };
+        if let ChainResult::Done(Err(ref err)) | ChainResult::Err(ref err) = result {
+            let handler_name = err.get_handler_name().unwrap_or(type_name::<H>());
+            let letter = DeadLetter::new(update, handler_name, err);
+            if let Err(store_err) = self.store.push(letter).await {
+                log::error!("Failed to save a dead letter: {store_err}");
+            }
+        }
+        result
+    }
+
+    fn allowed_updates(&self) -> HashSet<AllowedUpdate> {
+        self.handler.allowed_updates()
+    }
+
+    fn describe(&self) -> HandlerDescription {
+        self.handler.describe()
+    }
+}
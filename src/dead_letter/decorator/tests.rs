@@ -0,0 +1,174 @@
+use std::{
+    error::Error,
+    fmt,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use crate::{
+    core::{App, Chain, Context, MemoryDedupStore, Ref},
+    dead_letter::{DeadLetterExt, FilesystemDeadLetterStore},
+    handler::UpdateHandler,
+    types::Update,
+};
+
+use super::*;
+
+#[derive(Clone, Default)]
+struct Switch(Arc<AtomicBool>);
+
+#[derive(Debug)]
+struct ExampleError;
+
+impl fmt::Display for ExampleError {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        write!(out, "Example error")
+    }
+}
+
+impl Error for ExampleError {}
+
+async fn handler(Ref(Switch(is_ok)): Ref<Switch>) -> Result<(), ExampleError> {
+    if is_ok.load(Ordering::SeqCst) {
+        Ok(())
+    } else {
+        Err(ExampleError)
+    }
+}
+
+fn create_update(update_id: i64) -> Update {
+    serde_json::from_value(serde_json::json!({
+        "update_id": update_id,
+        "message": {
+            "message_id": 1111,
+            "date": 0,
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "chat": {"id": 1, "type": "private", "first_name": "test"},
+            "text": "test"
+        }
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn decorator() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let store = FilesystemDeadLetterStore::new(tmpdir.path().join("letters.jsonl"));
+    let switch = Switch::default();
+    let mut context = Context::default();
+    context.insert(switch.clone());
+    let app = App::new(context, handler.with_dead_letters(store.clone()));
+
+    app.handle(create_update(1)).await;
+    app.handle(create_update(2)).await;
+
+    let letters = store.take_all().await.unwrap();
+    assert_eq!(letters.len(), 2);
+    assert_eq!(letters[0].update.id, 1);
+    assert_eq!(letters[1].update.id, 2);
+    assert_eq!(letters[0].error, "Example error");
+    assert!(letters[0].handler.contains("handler"));
+
+    for letter in letters {
+        store.push(letter).await.unwrap();
+    }
+    // still failing, letters are saved again
+    assert_eq!(store.replay(&app).await.unwrap(), 2);
+    assert_eq!(store.take_all().await.unwrap().len(), 2);
+
+    app.handle(create_update(3)).await;
+    switch.0.store(true, Ordering::SeqCst);
+    assert_eq!(store.replay(&app).await.unwrap(), 1);
+    assert!(store.take_all().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn replay_with_dedup() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let store = FilesystemDeadLetterStore::new(tmpdir.path().join("letters.jsonl"));
+    let switch = Switch::default();
+    let mut context = Context::default();
+    context.insert(switch.clone());
+    let app = App::new(context, handler.with_dead_letters(store.clone()))
+        .with_dedup_store(MemoryDedupStore::new(10, std::time::Duration::from_secs(60)));
+
+    app.handle(create_update(1)).await;
+    assert_eq!(store.get_all().await.unwrap().len(), 1);
+
+    // replayed updates are not discarded as seen
+    assert_eq!(store.replay(&app).await.unwrap(), 1);
+    assert_eq!(store.get_all().await.unwrap().len(), 1);
+    switch.0.store(true, Ordering::SeqCst);
+    assert_eq!(store.replay(&app).await.unwrap(), 1);
+    assert!(store.get_all().await.unwrap().is_empty());
+}
+
+struct StoreObserver {
+    store: FilesystemDeadLetterStore,
+    sizes: std::sync::Mutex<Vec<usize>>,
+}
+
+impl UpdateHandler for StoreObserver {
+    async fn handle(&self, _update: Update) {
+        let size = self.store.get_all().await.unwrap().len();
+        self.sizes.lock().unwrap().push(size);
+    }
+}
+
+#[tokio::test]
+async fn replay_removes_handled_letters() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let store = FilesystemDeadLetterStore::new(tmpdir.path().join("letters.jsonl"));
+    let err = HandlerError::new(ExampleError);
+    for update_id in 1..=3 {
+        store
+            .push(DeadLetter::new(create_update(update_id), "handler", &err))
+            .await
+            .unwrap();
+    }
+    let observer = StoreObserver {
+        store: store.clone(),
+        sizes: Default::default(),
+    };
+    assert_eq!(store.replay(&observer).await.unwrap(), 3);
+    // a letter is kept in the store until its update is handled
+    assert_eq!(*observer.sizes.lock().unwrap(), vec![3, 2, 1]);
+    assert!(store.get_all().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn chain_handler_name() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let store = FilesystemDeadLetterStore::new(tmpdir.path().join("letters.jsonl"));
+    let mut context = Context::default();
+    context.insert(Switch::default());
+    let app = App::new(context, Chain::once().with(handler).with_dead_letters(store.clone()));
+    app.handle(create_update(1)).await;
+    let letters = store.take_all().await.unwrap();
+    assert_eq!(letters.len(), 1);
+    assert!(letters[0].handler.ends_with("::handler"));
+}
+
+async fn stopper(_: Update) -> std::ops::ControlFlow<()> {
+    std::ops::ControlFlow::Break(())
+}
+
+#[tokio::test]
+async fn stop() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let store = FilesystemDeadLetterStore::new(tmpdir.path().join("letters.jsonl"));
+    let mut context = Context::default();
+    context.insert(Switch::default());
+    let chain = Chain::all()
+        .with(stopper.with_dead_letters(store.clone()))
+        .with(handler.with_dead_letters(store.clone()));
+    let input = HandlerInput {
+        update: create_update(1),
+        context: Arc::new(context),
+        extensions: Default::default(),
+    };
+    assert!(matches!(chain.handle(input).await, ChainResult::Stop));
+    assert!(store.take_all().await.unwrap().is_empty());
+}
@@ -0,0 +1,25 @@
+use crate::{
+    core::{Handler, TryFromInput},
+    dead_letter::decorator::DeadLetterDecorator,
+};
+
+/// Provides a shortcut for wrapping a [`Handler`] by a [`DeadLetterDecorator`].
+pub trait DeadLetterExt<S, HI>: Sized {
+    /// Shortcut to wrap a [`Handler`] with a [`DeadLetterDecorator`].
+    ///
+    /// Example: `handler.with_dead_letters(store)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - A [`crate::dead_letter::DeadLetterStore`].
+    fn with_dead_letters(self, store: S) -> DeadLetterDecorator<S, Self, HI> {
+        DeadLetterDecorator::new(store, self)
+    }
+}
+
+impl<S, H, HI> DeadLetterExt<S, HI> for H
+where
+    H: Handler<HI>,
+    HI: TryFromInput,
+{
+}
@@ -0,0 +1,42 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{core::HandlerError, types::Update};
+
+/// Represents an update which could not be processed.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DeadLetter {
+    /// The failed update.
+    pub update: Update,
+    /// A name of the handler that produced the error.
+    pub handler: String,
+    /// Text of the error.
+    pub error: String,
+    /// Unix timestamp when the error has occurred.
+    pub created_at: u64,
+}
+
+impl DeadLetter {
+    /// Creates a new `DeadLetter`.
+    ///
+    /// # Arguments
+    ///
+    /// * `update` - The failed update.
+    /// * `handler` - A name of the handler that produced the error.
+    /// * `error` - The error.
+    pub fn new<T>(update: Update, handler: T, error: &HandlerError) -> Self
+    where
+        T: Into<String>,
+    {
+        Self {
+            update,
+            handler: handler.into(),
+            error: error.to_string(),
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|x| x.as_secs())
+                .unwrap_or_default(),
+        }
+    }
+}
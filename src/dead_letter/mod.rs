@@ -0,0 +1,6 @@
+mod decorator;
+mod ext;
+mod letter;
+mod store;
+
+pub use self::{decorator::*, ext::*, letter::*, store::*};
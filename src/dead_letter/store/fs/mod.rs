@@ -0,0 +1,169 @@
+use std::{error::Error, fmt, io::Error as IoError, path::PathBuf, sync::Arc};
+
+use serde_json::Error as JsonError;
+use tokio::{
+    fs::{OpenOptions, read_to_string, rename, write},
+    io::AsyncWriteExt,
+    sync::Mutex,
+};
+
+use crate::dead_letter::{letter::DeadLetter, store::DeadLetterStore};
+
+#[cfg(test)]
+mod tests;
+
+/// A [`DeadLetterStore`] which saves letters to a file in JSON Lines format.
+///
+/// Lines which could not be parsed are skipped and kept in the file.
+#[derive(Clone)]
+pub struct FilesystemDeadLetterStore {
+    path: PathBuf,
+    lock: Arc<Mutex<()>>,
+}
+
+impl FilesystemDeadLetterStore {
+    /// Creates a new `FilesystemDeadLetterStore`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - A path to the file; it will be created if it doesn't exist.
+    pub fn new<P>(path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self {
+            path: path.into(),
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    async fn read_lines(&self) -> Result<Vec<String>, FilesystemDeadLetterError> {
+        match read_to_string(&self.path).await {
+            Ok(data) => Ok(data
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(String::from)
+                .collect()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(err) => Err(FilesystemDeadLetterError::Io(err)),
+        }
+    }
+
+    async fn write_lines(&self, lines: &[String]) -> Result<(), FilesystemDeadLetterError> {
+        let mut data = lines.join("\n");
+        if !data.is_empty() {
+            data.push('\n');
+        }
+        // Write to a temporary file first, so the store is not corrupted when the process crashes
+        let tmp_path = self.path.with_extension("tmp");
+        write(&tmp_path, data).await.map_err(FilesystemDeadLetterError::Io)?;
+        rename(&tmp_path, &self.path)
+            .await
+            .map_err(FilesystemDeadLetterError::Io)
+    }
+
+    fn parse_line(&self, idx: usize, line: &str) -> Option<DeadLetter> {
+        match serde_json::from_str(line) {
+            Ok(letter) => Some(letter),
+            Err(err) => {
+                log::warn!(
+                    "Skipping invalid dead letter at {}:{}: {err}",
+                    self.path.display(),
+                    idx + 1
+                );
+                None
+            }
+        }
+    }
+}
+
+impl DeadLetterStore for FilesystemDeadLetterStore {
+    type Error = FilesystemDeadLetterError;
+
+    async fn push(&self, letter: DeadLetter) -> Result<(), Self::Error> {
+        let mut data = serde_json::to_vec(&letter).map_err(FilesystemDeadLetterError::Json)?;
+        data.push(b'\n');
+        let _lock = self.lock.lock().await;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(FilesystemDeadLetterError::Io)?;
+        file.write_all(&data).await.map_err(FilesystemDeadLetterError::Io)?;
+        file.flush().await.map_err(FilesystemDeadLetterError::Io)
+    }
+
+    async fn get_all(&self) -> Result<Vec<DeadLetter>, Self::Error> {
+        let _lock = self.lock.lock().await;
+        let lines = self.read_lines().await?;
+        Ok(lines
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, line)| self.parse_line(idx, line))
+            .collect())
+    }
+
+    async fn remove(&self, letter: &DeadLetter) -> Result<bool, Self::Error> {
+        let _lock = self.lock.lock().await;
+        let mut lines = self.read_lines().await?;
+        let position = lines
+            .iter()
+            .position(|line| serde_json::from_str::<DeadLetter>(line).is_ok_and(|x| &x == letter));
+        match position {
+            Some(idx) => {
+                lines.remove(idx);
+                self.write_lines(&lines).await?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn take_all(&self) -> Result<Vec<DeadLetter>, Self::Error> {
+        let _lock = self.lock.lock().await;
+        let lines = self.read_lines().await?;
+        if lines.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut letters = Vec::new();
+        let mut invalid = Vec::new();
+        for (idx, line) in lines.into_iter().enumerate() {
+            match self.parse_line(idx, &line) {
+                Some(letter) => letters.push(letter),
+                None => invalid.push(line),
+            }
+        }
+        self.write_lines(&invalid).await?;
+        Ok(letters)
+    }
+}
+
+/// An error when accessing a [`FilesystemDeadLetterStore`].
+#[derive(Debug)]
+pub enum FilesystemDeadLetterError {
+    /// Could not read or write the file.
+    Io(IoError),
+    /// Could not serialize or deserialize a letter.
+    Json(JsonError),
+}
+
+impl fmt::Display for FilesystemDeadLetterError {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        use self::FilesystemDeadLetterError::*;
+        match self {
+            Io(err) => write!(out, "Dead letter IO error: {err}"),
+            Json(err) => write!(out, "Dead letter JSON error: {err}"),
+        }
+    }
+}
+
+impl Error for FilesystemDeadLetterError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use self::FilesystemDeadLetterError::*;
+        match self {
+            Io(err) => Some(err),
+            Json(err) => Some(err),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{core::HandlerError, types::Update};
+
+use super::*;
+
+fn create_letter(update_id: i64) -> DeadLetter {
+    let update: Update = serde_json::from_value(serde_json::json!({
+        "update_id": update_id,
+        "message": {
+            "message_id": 1111,
+            "date": 0,
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "chat": {"id": 1, "type": "private", "first_name": "test"},
+            "text": "test"
+        }
+    }))
+    .unwrap();
+    let err = HandlerError::new(std::io::Error::other("test error"));
+    DeadLetter::new(update, "handler", &err)
+}
+
+#[tokio::test]
+async fn filesystem_store() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let path = tmpdir.path().join("letters.jsonl");
+    let store = FilesystemDeadLetterStore::new(&path);
+    assert!(store.take_all().await.unwrap().is_empty());
+
+    let first = create_letter(1);
+    let second = create_letter(2);
+    store.push(first.clone()).await.unwrap();
+    store.push(second.clone()).await.unwrap();
+    assert_eq!(read_to_string(&path).await.unwrap().lines().count(), 2);
+
+    // a new store reads letters saved by another instance
+    let store = FilesystemDeadLetterStore::new(&path);
+    assert_eq!(store.take_all().await.unwrap(), vec![first, second]);
+    assert!(store.take_all().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn filesystem_store_remove() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let path = tmpdir.path().join("letters.jsonl");
+    let store = FilesystemDeadLetterStore::new(&path);
+    let first = create_letter(1);
+    let second = create_letter(2);
+    assert!(!store.remove(&first).await.unwrap());
+    store.push(first.clone()).await.unwrap();
+    store.push(second.clone()).await.unwrap();
+    store.push(first.clone()).await.unwrap();
+
+    assert_eq!(
+        store.get_all().await.unwrap(),
+        vec![first.clone(), second.clone(), first.clone()]
+    );
+    assert!(store.remove(&first).await.unwrap());
+    assert_eq!(store.get_all().await.unwrap(), vec![second.clone(), first.clone()]);
+    assert!(store.remove(&second).await.unwrap());
+    assert!(store.remove(&first).await.unwrap());
+    assert!(store.get_all().await.unwrap().is_empty());
+    assert_eq!(read_to_string(&path).await.unwrap(), "");
+}
+
+#[tokio::test]
+async fn filesystem_store_invalid_data() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let path = tmpdir.path().join("letters.jsonl");
+    let letter = create_letter(1);
+    let data = format!("not a json\n{}\n", serde_json::to_string(&letter).unwrap());
+    tokio::fs::write(&path, data).await.unwrap();
+    let store = FilesystemDeadLetterStore::new(&path);
+    // invalid lines are skipped
+    assert_eq!(store.get_all().await.unwrap(), vec![letter.clone()]);
+    assert!(store.remove(&letter).await.unwrap());
+    assert_eq!(read_to_string(&path).await.unwrap(), "not a json\n");
+
+    store.push(letter.clone()).await.unwrap();
+    assert_eq!(store.take_all().await.unwrap(), vec![letter]);
+    // invalid data is kept
+    assert_eq!(read_to_string(&path).await.unwrap(), "not a json\n");
+}
@@ -0,0 +1,65 @@
+use std::{error::Error, future::Future};
+
+use crate::{core::without_dedup, dead_letter::letter::DeadLetter, handler::UpdateHandler};
+
+pub use self::fs::*;
+
+mod fs;
+
+/// Represents a storage for dead letters.
+pub trait DeadLetterStore: Send + Sync {
+    /// An error when accessing the store.
+    type Error: Error + Send + 'static;
+
+    /// Saves a letter.
+    ///
+    /// # Arguments
+    ///
+    /// * `letter` - The letter to save.
+    fn push(&self, letter: DeadLetter) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    /// Returns all letters in the order they were saved without removing them.
+    fn get_all(&self) -> impl Future<Output = Result<Vec<DeadLetter>, Self::Error>> + Send;
+
+    /// Removes the first letter equal to the given one.
+    ///
+    /// Returns `false` when the letter is not found.
+    ///
+    /// # Arguments
+    ///
+    /// * `letter` - The letter to remove.
+    fn remove(&self, letter: &DeadLetter) -> impl Future<Output = Result<bool, Self::Error>> + Send;
+
+    /// Removes all letters from the store and returns them in the order they were saved.
+    fn take_all(&self) -> impl Future<Output = Result<Vec<DeadLetter>, Self::Error>> + Send;
+
+    /// Passes all stored updates to a handler, e.g. [`crate::App`].
+    ///
+    /// Each letter is removed from the store after its update has been handled,
+    /// so letters are kept when the process stops in the middle of a replay.
+    /// Make sure that the handler is wrapped by [`crate::dead_letter::DeadLetterDecorator`]
+    /// with the same store in order to keep updates that failed again.
+    ///
+    /// Replayed updates are not checked by [`crate::App::with_dedup_store`].
+    ///
+    /// Returns a number of replayed updates.
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - The handler to process updates.
+    fn replay<H>(&self, handler: &H) -> impl Future<Output = Result<usize, Self::Error>> + Send
+    where
+        H: UpdateHandler + Sync,
+    {
+        async move {
+            let letters = self.get_all().await?;
+            let count = letters.len();
+            for letter in letters {
+                log::info!("Replaying update {} failed in '{}'", letter.update.id, letter.handler);
+                without_dedup(handler.handle(letter.update.clone())).await;
+                self.remove(&letter).await?;
+            }
+            Ok(count)
+        }
+    }
+}
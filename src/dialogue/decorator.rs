@@ -1,10 +1,11 @@
-use std::{error::Error, marker::PhantomData};
+use std::{collections::HashSet, error::Error, marker::PhantomData};
 
 use seance::{Session, backend::SessionBackend};
 
 use crate::{
-    core::{Handler, HandlerError, HandlerInput, HandlerResult, TryFromInput},
+    core::{Handler, HandlerDescription, HandlerError, HandlerInput, HandlerResult, TryFromInput, clear_update_cache},
     dialogue::{error::DialogueError, result::DialogueResult, state::DialogueState},
+    types::AllowedUpdate,
 };
 
 /// A decorator for dialogue handlers.
@@ -68,8 +69,8 @@ where
     async fn handle(&self, input: HandlerInput) -> Self::Output {
         let handler_input = match HI::try_from_input(input.clone()).await {
             Ok(Some(input)) => input,
-            Ok(None) => return Err(HandlerError::new(DialogueError::ConvertHandlerInput)),
-            Err(err) => return Err(HandlerError::new(err)),
+            Ok(None) => return Err(HandlerError::convert(DialogueError::ConvertHandlerInput)),
+            Err(err) => return Err(HandlerError::convert(err)),
         };
         let result = match self.handler.handle(handler_input).await {
             Ok(result) => result.into(),
@@ -79,7 +80,7 @@ where
         let mut session = match <Session<B>>::try_from_input(input).await {
             Ok(Some(session)) => session,
             Ok(None) => unreachable!("TryFromInput implementation for Session<B> never returns None"),
-            Err(err) => return Err(HandlerError::new(err)),
+            Err(err) => return Err(HandlerError::convert(err)),
         };
         let session_key = HS::session_key();
 
@@ -88,15 +89,29 @@ where
                 if let Err(err) = session.set(session_key, &state).await {
                     return Err(HandlerError::new(err));
                 }
+                clear_update_cache();
+                #[cfg(feature = "metrics")]
+                crate::metrics::registry().record_dialogue_transition("next");
             }
             DialogueResult::Exit => {
                 // Explicitly remove the state from the session to make sure that the dialog will not run again.
                 if let Err(err) = session.remove(session_key).await {
                     return Err(HandlerError::new(err));
                 }
+                clear_update_cache();
+                #[cfg(feature = "metrics")]
+                crate::metrics::registry().record_dialogue_transition("exit");
             }
         }
 
         Ok(())
     }
+
+    fn allowed_updates(&self) -> HashSet<AllowedUpdate> {
+        self.handler.allowed_updates()
+    }
+
+    fn describe(&self) -> HandlerDescription {
+        self.handler.describe()
+    }
 }
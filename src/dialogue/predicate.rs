@@ -1,9 +1,9 @@
-use std::marker::PhantomData;
+use std::{any::type_name, marker::PhantomData};
 
 use seance::{Session, backend::SessionBackend};
 
 use crate::{
-    core::{Handler, HandlerError, HandlerInput, PredicateResult, TryFromInput},
+    core::{Handler, HandlerDescription, HandlerError, HandlerInput, PredicateResult, TryFromInput},
     dialogue::state::DialogueState,
 };
 
@@ -64,7 +64,7 @@ where
         let mut session = match <Session<B>>::try_from_input(input.clone()).await {
             Ok(Some(session)) => session,
             Ok(None) => unreachable!("TryFromInput implementation for Session<B> never returns None"),
-            Err(err) => return PredicateResult::Err(HandlerError::new(err)),
+            Err(err) => return PredicateResult::Err(HandlerError::convert(err)),
         };
         let session_key = HS::session_key();
         match session.get::<&str, HS>(&session_key).await {
@@ -77,10 +77,18 @@ where
                 match PI::try_from_input(input.clone()).await {
                     Ok(Some(predicate_input)) => self.predicate.handle(predicate_input).await.into(),
                     Ok(None) => PredicateResult::False,
-                    Err(err) => PredicateResult::Err(HandlerError::new(err)),
+                    Err(err) => PredicateResult::Err(HandlerError::convert(err)),
                 }
             }
             Err(err) => PredicateResult::Err(HandlerError::new(err)),
         }
     }
+
+    fn describe(&self) -> HandlerDescription {
+        HandlerDescription::new(format!(
+            "dialogue {} or {}",
+            type_name::<HS>(),
+            self.predicate.describe().get_name()
+        ))
+    }
 }
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use tempfile::tempdir;
 
 use crate::{
-    core::{Chain, Context, Handler, HandlerInput, PredicateOutput, TryFromInput},
+    core::{Chain, Context, Handler, HandlerInput, IntoHandlerResult, PredicateOutput, TryFromInput},
     session::{SessionManager, backend::fs::FilesystemBackend},
     types::Text,
 };
@@ -50,7 +50,11 @@ fn create_input(context: Arc<Context>, text: &str) -> HandlerInput {
         }
     }))
     .unwrap();
-    HandlerInput { context, update }
+    HandlerInput {
+        context,
+        update,
+        extensions: Default::default(),
+    }
 }
 
 fn create_context() -> Arc<Context> {
@@ -109,7 +113,11 @@ async fn dialogue_in_chain_skipped() {
     let chain = Chain::once().with(handler).with(skip_handler);
     let input = create_input(context.clone(), "skipped");
     let mut session = get_session(input.clone()).await;
-    chain.handle(input).await.expect("Failed to run chain handler");
+    chain
+        .handle(input)
+        .await
+        .into_result()
+        .expect("Failed to run chain handler");
     let is_skipped: bool = session
         .get("is_skipped")
         .await
@@ -0,0 +1,84 @@
+use std::{
+    io::{Error as IoError, ErrorKind},
+    str::from_utf8,
+    time::Duration,
+};
+
+use tokio::{io::AsyncReadExt, net::TcpStream, time::timeout};
+
+#[cfg(test)]
+mod tests;
+
+const MAX_HEAD_SIZE: usize = 8192;
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A minimal HTTP request for internal servers.
+#[derive(Debug)]
+pub(crate) struct Request {
+    head: String,
+    body: Vec<u8>,
+}
+
+impl Request {
+    /// Returns a method of the request, e.g. `GET`.
+    #[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+    pub(crate) fn get_method(&self) -> &str {
+        self.head.split_whitespace().next().unwrap_or_default()
+    }
+
+    /// Returns a path of the request.
+    #[cfg_attr(not(feature = "replay"), allow(dead_code))]
+    pub(crate) fn get_path(&self) -> &str {
+        self.head.split_whitespace().nth(1).unwrap_or_default()
+    }
+
+    /// Returns a body of the request.
+    #[cfg_attr(not(feature = "replay"), allow(dead_code))]
+    pub(crate) fn into_body(self) -> Vec<u8> {
+        self.body
+    }
+}
+
+/// Reads a request from the stream.
+///
+/// Fails when the request is not received in 10 seconds,
+/// so a client can not keep a connection open forever.
+pub(crate) async fn read_request(stream: &mut TcpStream) -> Result<Request, IoError> {
+    timeout(READ_TIMEOUT, read(stream))
+        .await
+        .map_err(|_| IoError::new(ErrorKind::TimedOut, "request timed out"))?
+}
+
+async fn read(stream: &mut TcpStream) -> Result<Request, IoError> {
+    let mut data = Vec::new();
+    let mut buf = [0; 1024];
+    let head_end = loop {
+        if let Some(position) = data.windows(4).position(|x| x == b"\r\n\r\n") {
+            break position + 4;
+        }
+        if data.len() >= MAX_HEAD_SIZE {
+            return Err(IoError::other("headers are too large"));
+        }
+        let len = stream.read(&mut buf).await?;
+        if len == 0 {
+            return Err(IoError::other("unexpected end of request"));
+        }
+        data.extend_from_slice(&buf[..len]);
+    };
+    let mut body = data.split_off(head_end);
+    let head = String::from(from_utf8(&data).map_err(IoError::other)?);
+    let content_length = head
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or_default();
+    while body.len() < content_length {
+        let len = stream.read(&mut buf).await?;
+        if len == 0 {
+            break;
+        }
+        body.extend_from_slice(&buf[..len]);
+    }
+    Ok(Request { head, body })
+}
@@ -0,0 +1,31 @@
+use tokio::{io::AsyncWriteExt, net::TcpListener};
+
+use super::*;
+
+async fn connect() -> (TcpStream, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let client = TcpStream::connect(listener.local_addr().unwrap()).await.unwrap();
+    let (server, _) = listener.accept().await.unwrap();
+    (client, server)
+}
+
+#[tokio::test]
+async fn request() {
+    let (mut client, mut server) = connect().await;
+    client
+        .write_all(b"POST /path HTTP/1.1\r\nContent-Length: 4\r\n\r\nbody")
+        .await
+        .unwrap();
+    let request = read_request(&mut server).await.unwrap();
+    assert_eq!(request.get_method(), "POST");
+    assert_eq!(request.get_path(), "/path");
+    assert_eq!(request.into_body(), b"body");
+}
+
+#[tokio::test(start_paused = true)]
+async fn incomplete_request() {
+    let (mut client, mut server) = connect().await;
+    client.write_all(b"GET /path HTTP/1.1\r\n").await.unwrap();
+    let err = read_request(&mut server).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::TimedOut);
+}
@@ -7,22 +7,45 @@ pub use self::core::*;
 
 mod core;
 
+#[cfg(any(feature = "metrics", feature = "replay"))]
+mod http;
+
 /// Access control
 #[cfg(feature = "access")]
 #[cfg_attr(nightly, doc(cfg(feature = "access")))]
 pub mod access;
 
+/// Dead-letter support
+#[cfg(feature = "dead-letter")]
+#[cfg_attr(nightly, doc(cfg(feature = "dead-letter")))]
+pub mod dead_letter;
+
 /// Dialogue support
 #[cfg(feature = "dialogue")]
 #[cfg_attr(nightly, doc(cfg(feature = "dialogue")))]
 pub mod dialogue;
 
+/// Metrics support
+#[cfg(feature = "metrics")]
+#[cfg_attr(nightly, doc(cfg(feature = "metrics")))]
+pub mod metrics;
+
 /// Ratelimit support
 #[cfg(feature = "ratelimit")]
 #[cfg_attr(nightly, doc(cfg(feature = "ratelimit")))]
 pub mod ratelimit;
 
+/// Update recording and replay
+#[cfg(feature = "replay")]
+#[cfg_attr(nightly, doc(cfg(feature = "replay")))]
+pub mod replay;
+
 /// Session support
 #[cfg(feature = "session")]
 #[cfg_attr(nightly, doc(cfg(feature = "session")))]
 pub mod session;
+
+/// Tower interoperability
+#[cfg(feature = "tower")]
+#[cfg_attr(nightly, doc(cfg(feature = "tower")))]
+pub mod tower;
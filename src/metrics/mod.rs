@@ -0,0 +1,4 @@
+pub use self::{registry::*, server::*};
+
+mod registry;
+mod server;
@@ -0,0 +1,220 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{Mutex, MutexGuard, OnceLock},
+    time::Duration,
+};
+
+use crate::core::ChainResult;
+
+#[cfg(test)]
+mod tests;
+
+const LATENCY_BUCKETS: [f64; 11] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// Returns the global registry.
+///
+/// Metrics are collected by [`crate::App`], [`crate::Chain`] and predicates of the crate.
+pub fn registry() -> &'static MetricsRegistry {
+    static REGISTRY: OnceLock<MetricsRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(MetricsRegistry::default)
+}
+
+/// Stores collected metrics.
+#[derive(Default)]
+pub struct MetricsRegistry {
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    updates: BTreeMap<&'static str, u64>,
+    handlers: BTreeMap<String, HandlerStats>,
+    access_denials: u64,
+    ratelimit_discards: u64,
+    dialogue_transitions: BTreeMap<&'static str, u64>,
+}
+
+#[derive(Default)]
+struct HandlerStats {
+    invocations: u64,
+    skips: u64,
+    errors: u64,
+    latency: Histogram,
+}
+
+struct Histogram {
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self {
+            buckets: [0; LATENCY_BUCKETS.len()],
+            count: 0,
+            sum: 0.0,
+        }
+    }
+}
+
+impl Histogram {
+    fn observe(&mut self, value: f64) {
+        for (bucket, bound) in self.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if value <= bound {
+                *bucket += 1;
+            }
+        }
+        self.count += 1;
+        self.sum += value;
+    }
+}
+
+impl MetricsRegistry {
+    /// Renders metrics in the Prometheus text format.
+    pub fn render(&self) -> String {
+        let state = self.lock();
+        let mut out = String::new();
+
+        write_header(
+            &mut out,
+            "carapax_updates_total",
+            "Number of received updates.",
+            "counter",
+        );
+        for (kind, value) in &state.updates {
+            let _ = writeln!(out, "carapax_updates_total{{kind=\"{kind}\"}} {value}");
+        }
+
+        let handlers = &state.handlers;
+        let name = "carapax_handler_invocations_total";
+        write_handler_counter(&mut out, name, "Number of handler invocations.", handlers, |x| {
+            x.invocations
+        });
+        let name = "carapax_handler_skips_total";
+        write_handler_counter(
+            &mut out,
+            name,
+            "Number of updates skipped by a handler.",
+            handlers,
+            |x| x.skips,
+        );
+        let name = "carapax_handler_errors_total";
+        write_handler_counter(
+            &mut out,
+            name,
+            "Number of errors returned by a handler.",
+            handlers,
+            |x| x.errors,
+        );
+
+        let name = "carapax_handler_duration_seconds";
+        write_header(&mut out, name, "Handler execution time in seconds.", "histogram");
+        for (handler, stats) in &state.handlers {
+            let handler = escape_label(handler);
+            let latency = &stats.latency;
+            for (bound, value) in LATENCY_BUCKETS.iter().zip(latency.buckets) {
+                let _ = writeln!(out, "{name}_bucket{{handler=\"{handler}\",le=\"{bound}\"}} {value}");
+            }
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{handler=\"{handler}\",le=\"+Inf\"}} {}",
+                latency.count
+            );
+            let _ = writeln!(out, "{name}_sum{{handler=\"{handler}\"}} {}", latency.sum);
+            let _ = writeln!(out, "{name}_count{{handler=\"{handler}\"}} {}", latency.count);
+        }
+
+        let name = "carapax_access_denials_total";
+        write_header(
+            &mut out,
+            name,
+            "Number of updates denied by an access policy.",
+            "counter",
+        );
+        let _ = writeln!(out, "{name} {}", state.access_denials);
+
+        let name = "carapax_ratelimit_discards_total";
+        write_header(
+            &mut out,
+            name,
+            "Number of updates discarded by a rate limiter.",
+            "counter",
+        );
+        let _ = writeln!(out, "{name} {}", state.ratelimit_discards);
+
+        let name = "carapax_dialogue_transitions_total";
+        write_header(&mut out, name, "Number of dialogue state transitions.", "counter");
+        for (transition, value) in &state.dialogue_transitions {
+            let _ = writeln!(out, "{name}{{transition=\"{transition}\"}} {value}");
+        }
+
+        out
+    }
+
+    pub(crate) fn record_update(&self, kind: &'static str) {
+        *self.lock().updates.entry(kind).or_default() += 1;
+    }
+
+    pub(crate) fn record_handler(&self, handler: &str, result: &ChainResult, duration: Duration) {
+        let mut state = self.lock();
+        let stats = match state.handlers.get_mut(handler) {
+            Some(stats) => stats,
+            None => state.handlers.entry(handler.to_string()).or_default(),
+        };
+        match result {
+            ChainResult::Done(result) => {
+                stats.invocations += 1;
+                if result.is_err() {
+                    stats.errors += 1;
+                }
+                stats.latency.observe(duration.as_secs_f64());
+            }
+            ChainResult::Stop => {
+                stats.invocations += 1;
+                stats.latency.observe(duration.as_secs_f64());
+            }
+            ChainResult::Err(_) => stats.errors += 1,
+            ChainResult::Skipped => stats.skips += 1,
+        }
+    }
+
+    #[cfg_attr(not(feature = "access"), allow(dead_code))]
+    pub(crate) fn record_access_denial(&self) {
+        self.lock().access_denials += 1;
+    }
+
+    #[cfg_attr(not(feature = "ratelimit"), allow(dead_code))]
+    pub(crate) fn record_ratelimit_discard(&self) {
+        self.lock().ratelimit_discards += 1;
+    }
+
+    #[cfg_attr(not(feature = "dialogue"), allow(dead_code))]
+    pub(crate) fn record_dialogue_transition(&self, transition: &'static str) {
+        *self.lock().dialogue_transitions.entry(transition).or_default() += 1;
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+fn write_header(out: &mut String, name: &str, help: &str, kind: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn write_handler_counter<F>(out: &mut String, name: &str, help: &str, handlers: &BTreeMap<String, HandlerStats>, f: F)
+where
+    F: Fn(&HandlerStats) -> u64,
+{
+    write_header(out, name, help, "counter");
+    for (handler, stats) in handlers {
+        let _ = writeln!(out, "{name}{{handler=\"{}\"}} {}", escape_label(handler), f(stats));
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
@@ -0,0 +1,63 @@
+use crate::core::HandlerError;
+
+use super::*;
+
+#[derive(Debug)]
+struct ExampleError;
+
+impl std::fmt::Display for ExampleError {
+    fn fmt(&self, out: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(out, "Example error")
+    }
+}
+
+impl std::error::Error for ExampleError {}
+
+#[test]
+fn render() {
+    let registry = MetricsRegistry::default();
+    registry.record_update("message");
+    registry.record_update("message");
+    registry.record_update("callback_query");
+    let handler = "handler<\"x\">";
+    registry.record_handler(handler, &ChainResult::Done(Ok(())), Duration::from_millis(20));
+    registry.record_handler(
+        handler,
+        &ChainResult::Done(Err(HandlerError::new(ExampleError))),
+        Duration::from_secs(20),
+    );
+    registry.record_handler(
+        handler,
+        &ChainResult::Err(HandlerError::new(ExampleError)),
+        Duration::ZERO,
+    );
+    registry.record_handler(handler, &ChainResult::Skipped, Duration::ZERO);
+    registry.record_access_denial();
+    registry.record_ratelimit_discard();
+    registry.record_dialogue_transition("next");
+    registry.record_dialogue_transition("exit");
+    registry.record_dialogue_transition("next");
+
+    let output = registry.render();
+    let lines: Vec<&str> = output.lines().filter(|x| !x.starts_with('#')).collect();
+    for expected in [
+        r#"carapax_updates_total{kind="callback_query"} 1"#,
+        r#"carapax_updates_total{kind="message"} 2"#,
+        r#"carapax_handler_invocations_total{handler="handler<\"x\">"} 2"#,
+        r#"carapax_handler_skips_total{handler="handler<\"x\">"} 1"#,
+        r#"carapax_handler_errors_total{handler="handler<\"x\">"} 2"#,
+        r#"carapax_handler_duration_seconds_bucket{handler="handler<\"x\">",le="0.01"} 0"#,
+        r#"carapax_handler_duration_seconds_bucket{handler="handler<\"x\">",le="0.025"} 1"#,
+        r#"carapax_handler_duration_seconds_bucket{handler="handler<\"x\">",le="10"} 1"#,
+        r#"carapax_handler_duration_seconds_bucket{handler="handler<\"x\">",le="+Inf"} 2"#,
+        r#"carapax_handler_duration_seconds_sum{handler="handler<\"x\">"} 20.02"#,
+        r#"carapax_handler_duration_seconds_count{handler="handler<\"x\">"} 2"#,
+        "carapax_access_denials_total 1",
+        "carapax_ratelimit_discards_total 1",
+        r#"carapax_dialogue_transitions_total{transition="exit"} 1"#,
+        r#"carapax_dialogue_transitions_total{transition="next"} 2"#,
+    ] {
+        assert!(lines.contains(&expected), "{expected} not found in {output}");
+    }
+    assert!(output.contains("# TYPE carapax_handler_duration_seconds histogram\n"));
+}
@@ -0,0 +1,49 @@
+use std::io::Error as IoError;
+
+use tokio::{
+    io::AsyncWriteExt,
+    net::{TcpListener, TcpStream},
+};
+
+use crate::{http::read_request, metrics::registry::registry};
+
+#[cfg(test)]
+mod tests;
+
+/// Serves metrics of the global registry over HTTP.
+///
+/// Every `GET` request gets a response with [`crate::metrics::MetricsRegistry::render`] output,
+/// so the listener should not be shared with other services.
+/// A connection is closed when a request is not received in 10 seconds.
+///
+/// Use [`crate::metrics::registry`] to mount metrics on an existing HTTP server instead,
+/// e.g. on a router created from [`crate::handler::WebhookServer`].
+///
+/// # Arguments
+///
+/// * `listener` - A listener to accept connections.
+pub async fn serve_metrics(listener: TcpListener) -> Result<(), IoError> {
+    loop {
+        let (stream, _) = listener.accept().await?;
+        tokio::spawn(async move {
+            if let Err(err) = respond(stream).await {
+                log::warn!("Failed to serve metrics: {err}");
+            }
+        });
+    }
+}
+
+async fn respond(mut stream: TcpStream) -> Result<(), IoError> {
+    let request = read_request(&mut stream).await?;
+    let response = if request.get_method() == "GET" {
+        let body = registry().render();
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+    } else {
+        String::from("HTTP/1.1 405 Method Not Allowed\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+    };
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
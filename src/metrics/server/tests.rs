@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use tokio::io::AsyncReadExt;
+
+use crate::{
+    core::{App, Chain, Context, HandlerResult},
+    handler::UpdateHandler,
+    types::Update,
+};
+
+use super::*;
+
+async fn metrics_server_handler(_: Update) -> HandlerResult {
+    tokio::time::sleep(Duration::from_millis(1)).await;
+    Ok(())
+}
+
+async fn request(address: std::net::SocketAddr, request: &str) -> String {
+    let mut stream = TcpStream::connect(address).await.unwrap();
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    response
+}
+
+#[tokio::test]
+async fn serve() {
+    let update: Update = serde_json::from_value(serde_json::json!({
+        "update_id": 1,
+        "message": {
+            "message_id": 1111,
+            "date": 0,
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "chat": {"id": 1, "type": "private", "first_name": "test"},
+            "text": "test"
+        }
+    }))
+    .unwrap();
+    let app = App::new(Context::default(), Chain::all().with(metrics_server_handler));
+    app.handle(update).await;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let server = tokio::spawn(serve_metrics(listener));
+
+    let response = request(address, "GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").await;
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains("Content-Type: text/plain; version=0.0.4; charset=utf-8\r\n"));
+    assert!(response.contains("carapax_updates_total{kind=\"message\"}"));
+    assert!(response.contains("::metrics_server_handler\"} 1\n"));
+
+    let response = request(address, "POST /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").await;
+    assert!(response.starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+
+    server.abort();
+}
@@ -10,7 +10,7 @@ use governor::{
 pub use nonzero_ext::nonzero;
 
 use crate::{
-    core::{Handler, PredicateResult},
+    core::{Handler, HandlerDescription, PredicateResult},
     ratelimit::{
         jitter::NoJitter,
         method::{MethodDiscard, MethodWait},
@@ -90,10 +90,16 @@ impl Handler<()> for DirectRateLimitPredicate<NoJitter, MethodDiscard> {
             Ok(_) => PredicateResult::True,
             Err(_) => {
                 log::info!("DirectRateLimitPredicate: update discarded");
+                #[cfg(feature = "metrics")]
+                crate::metrics::registry().record_ratelimit_discard();
                 PredicateResult::False
             }
         }
     }
+
+    fn describe(&self) -> HandlerDescription {
+        HandlerDescription::new("ratelimit (discard)")
+    }
 }
 
 impl Handler<()> for DirectRateLimitPredicate<NoJitter, MethodWait> {
@@ -103,6 +109,10 @@ impl Handler<()> for DirectRateLimitPredicate<NoJitter, MethodWait> {
         self.limiter.until_ready().await;
         PredicateResult::True
     }
+
+    fn describe(&self) -> HandlerDescription {
+        HandlerDescription::new("ratelimit (wait)")
+    }
 }
 
 impl Handler<()> for DirectRateLimitPredicate<Jitter, MethodWait> {
@@ -112,4 +122,8 @@ impl Handler<()> for DirectRateLimitPredicate<Jitter, MethodWait> {
         self.limiter.until_ready_with_jitter(self.jitter).await;
         PredicateResult::True
     }
+
+    fn describe(&self) -> HandlerDescription {
+        HandlerDescription::new("ratelimit (wait with jitter)")
+    }
 }
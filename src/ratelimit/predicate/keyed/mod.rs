@@ -1,4 +1,4 @@
-use std::{collections::HashSet, sync::Arc};
+use std::{any::type_name, collections::HashSet, sync::Arc};
 
 pub use governor::{Jitter, Quota};
 use governor::{RateLimiter, clock::DefaultClock, state::keyed::DefaultKeyedStateStore};
@@ -6,7 +6,7 @@ use governor::{RateLimiter, clock::DefaultClock, state::keyed::DefaultKeyedState
 pub use nonzero_ext::nonzero;
 
 use crate::{
-    core::{Handler, PredicateResult},
+    core::{Handler, HandlerDescription, PredicateResult},
     ratelimit::{
         jitter::NoJitter,
         key::Key,
@@ -126,6 +126,8 @@ where
                 Ok(_) => PredicateResult::True,
                 Err(_) => {
                     log::info!("KeyedRateLimitPredicate: update discarded");
+                    #[cfg(feature = "metrics")]
+                    crate::metrics::registry().record_ratelimit_discard();
                     PredicateResult::False
                 }
             }
@@ -133,6 +135,10 @@ where
             PredicateResult::True
         }
     }
+
+    fn describe(&self) -> HandlerDescription {
+        HandlerDescription::new(format!("ratelimit by {} (discard)", type_name::<K>()))
+    }
 }
 
 impl<K> Handler<K> for KeyedRateLimitPredicate<K, NoJitter, MethodWait>
@@ -149,6 +155,10 @@ where
             PredicateResult::True
         }
     }
+
+    fn describe(&self) -> HandlerDescription {
+        HandlerDescription::new(format!("ratelimit by {} (wait)", type_name::<K>()))
+    }
 }
 
 impl<K> Handler<K> for KeyedRateLimitPredicate<K, Jitter, MethodWait>
@@ -165,4 +175,8 @@ where
             PredicateResult::True
         }
     }
+
+    fn describe(&self) -> HandlerDescription {
+        HandlerDescription::new(format!("ratelimit by {} (wait with jitter)", type_name::<K>()))
+    }
 }
@@ -0,0 +1,66 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// A source of the current time.
+///
+/// Insert the clock into the [`crate::Context`] and use [`crate::Ref<Clock>`] in handlers
+/// instead of [`SystemTime::now`] in order to get reproducible results
+/// when updates are replayed by [`crate::replay::ReplayRunner`].
+#[derive(Clone, Debug, Default)]
+pub struct Clock {
+    now: Option<Arc<AtomicU64>>,
+}
+
+impl Clock {
+    /// Creates a clock which returns the system time.
+    pub fn system() -> Self {
+        Self { now: None }
+    }
+
+    /// Creates a virtual clock which returns a given time until it is changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The initial time.
+    pub fn virtual_time(now: SystemTime) -> Self {
+        Self {
+            now: Some(Arc::new(AtomicU64::new(to_millis(now)))),
+        }
+    }
+
+    /// Returns the current time.
+    pub fn now(&self) -> SystemTime {
+        match self.now {
+            Some(ref now) => UNIX_EPOCH + Duration::from_millis(now.load(Ordering::SeqCst)),
+            None => SystemTime::now(),
+        }
+    }
+
+    /// Sets the current time of a virtual clock.
+    ///
+    /// Has no effect on the system clock.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The new time.
+    pub fn set(&self, now: SystemTime) {
+        if let Some(ref value) = self.now {
+            value.store(to_millis(now), Ordering::SeqCst);
+        }
+    }
+}
+
+fn to_millis(value: SystemTime) -> u64 {
+    value
+        .duration_since(UNIX_EPOCH)
+        .map(|x| x.as_millis() as u64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,15 @@
+use super::*;
+
+#[test]
+fn clock() {
+    let clock = Clock::system();
+    let now = SystemTime::now();
+    clock.set(UNIX_EPOCH);
+    assert!(clock.now() >= now);
+
+    let clock = Clock::virtual_time(UNIX_EPOCH + Duration::from_secs(10));
+    let cloned = clock.clone();
+    assert_eq!(clock.now(), UNIX_EPOCH + Duration::from_secs(10));
+    cloned.set(UNIX_EPOCH + Duration::from_millis(20_500));
+    assert_eq!(clock.now(), UNIX_EPOCH + Duration::from_millis(20_500));
+}
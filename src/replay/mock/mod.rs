@@ -0,0 +1,152 @@
+use std::{
+    collections::HashMap,
+    io::Error as IoError,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use serde_json::{Error as JsonError, Value as JsonValue};
+use tokio::{
+    io::AsyncWriteExt,
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+
+use crate::{
+    api::{Client, ClientError},
+    http::read_request,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// A fake Telegram Bot API server.
+///
+/// Records all received requests and responds with a configured result.
+/// When a result for a method is not configured, `true` is returned.
+#[derive(Clone, Debug, Default)]
+pub struct MockApi {
+    results: HashMap<String, JsonValue>,
+}
+
+impl MockApi {
+    /// Sets a result for a method.
+    ///
+    /// # Arguments
+    ///
+    /// * `method` - A name of the method, e.g. `sendMessage`.
+    /// * `result` - A value of the `result` field in the response.
+    pub fn with_result<M>(mut self, method: M, result: JsonValue) -> Self
+    where
+        M: Into<String>,
+    {
+        self.results.insert(method.into(), result);
+        self
+    }
+
+    /// Starts the server on a random local port.
+    pub async fn start(self) -> Result<MockApiServer, IoError> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let task = tokio::spawn(serve(listener, Arc::new(self.results), requests.clone()));
+        Ok(MockApiServer {
+            address,
+            requests,
+            task,
+        })
+    }
+}
+
+/// A running [`MockApi`] server.
+///
+/// The server is stopped when the value is dropped.
+pub struct MockApiServer {
+    address: SocketAddr,
+    requests: Arc<Mutex<Vec<MockRequest>>>,
+    task: JoinHandle<()>,
+}
+
+impl MockApiServer {
+    /// Returns an address of the server.
+    pub fn get_address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Creates a client sending requests to the server.
+    pub fn get_client(&self) -> Result<Client, ClientError> {
+        Client::new("mock").map(|client| client.with_host(format!("http://{}", self.address)))
+    }
+
+    /// Returns all requests received so far.
+    pub fn get_requests(&self) -> Vec<MockRequest> {
+        self.requests.lock().expect("Failed to lock requests").clone()
+    }
+}
+
+impl Drop for MockApiServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// A request received by a [`MockApiServer`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct MockRequest {
+    /// A name of the method.
+    pub method: String,
+    /// A body of the request.
+    pub body: Vec<u8>,
+}
+
+impl MockRequest {
+    /// Parses the body as JSON.
+    pub fn get_json(&self) -> Result<JsonValue, JsonError> {
+        serde_json::from_slice(&self.body)
+    }
+}
+
+async fn serve(
+    listener: TcpListener,
+    results: Arc<HashMap<String, JsonValue>>,
+    requests: Arc<Mutex<Vec<MockRequest>>>,
+) {
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(err) => {
+                log::warn!("Mock API failed to accept connection: {err}");
+                continue;
+            }
+        };
+        let results = results.clone();
+        let requests = requests.clone();
+        tokio::spawn(async move {
+            if let Err(err) = respond(stream, &results, &requests).await {
+                log::warn!("Mock API failed to respond: {err}");
+            }
+        });
+    }
+}
+
+async fn respond(
+    mut stream: TcpStream,
+    results: &HashMap<String, JsonValue>,
+    requests: &Mutex<Vec<MockRequest>>,
+) -> Result<(), IoError> {
+    let request = read_request(&mut stream).await?;
+    let method = request.get_path().rsplit('/').next().unwrap_or_default().to_string();
+    let body = request.into_body();
+    let result = results.get(&method).cloned().unwrap_or(JsonValue::Bool(true));
+    requests
+        .lock()
+        .expect("Failed to lock requests")
+        .push(MockRequest { method, body });
+    let response = serde_json::json!({"ok": true, "result": result}).to_string();
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+        response.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
@@ -0,0 +1,36 @@
+use crate::types::{ChatPeerId, SendMessage};
+
+use super::*;
+
+#[tokio::test]
+async fn mock_api() {
+    let server = MockApi::default()
+        .with_result(
+            "sendMessage",
+            serde_json::json!({
+                "message_id": 1,
+                "date": 0,
+                "chat": {"id": 1, "type": "private", "first_name": "test"},
+                "text": "reply"
+            }),
+        )
+        .start()
+        .await
+        .unwrap();
+    let client = server.get_client().unwrap();
+    let message = client
+        .execute(SendMessage::new(ChatPeerId::from(1), "reply"))
+        .await
+        .unwrap();
+    assert_eq!(message.id, 1);
+    assert!(client.execute(crate::types::DeleteWebhook::default()).await.unwrap());
+
+    let requests = server.get_requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].method, "sendMessage");
+    assert_eq!(
+        requests[0].get_json().unwrap(),
+        serde_json::json!({"chat_id": 1, "text": "reply"})
+    );
+    assert_eq!(requests[1].method, "deleteWebhook");
+}
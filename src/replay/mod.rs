@@ -0,0 +1,7 @@
+pub use self::{clock::*, mock::*, record::*, recorder::*, runner::*};
+
+mod clock;
+mod mock;
+mod record;
+mod recorder;
+mod runner;
@@ -0,0 +1,37 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::Update;
+
+/// Represents an update saved by [`crate::replay::UpdateRecorder`].
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct RecordedUpdate {
+    /// The received update.
+    pub update: Update,
+    /// Unix timestamp in milliseconds when the update has been received.
+    pub received_at: u64,
+}
+
+impl RecordedUpdate {
+    /// Creates a new `RecordedUpdate`.
+    ///
+    /// # Arguments
+    ///
+    /// * `update` - The received update.
+    /// * `received_at` - The time when the update has been received.
+    pub fn new(update: Update, received_at: SystemTime) -> Self {
+        Self {
+            update,
+            received_at: received_at
+                .duration_since(UNIX_EPOCH)
+                .map(|x| x.as_millis() as u64)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns the time when the update has been received.
+    pub fn get_received_at(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(self.received_at)
+    }
+}
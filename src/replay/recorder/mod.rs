@@ -0,0 +1,80 @@
+use std::{path::PathBuf, sync::Arc};
+
+use tokio::{fs::OpenOptions, io::AsyncWriteExt, sync::Mutex};
+
+use crate::{
+    handler::UpdateHandler,
+    replay::{clock::Clock, record::RecordedUpdate},
+    types::Update,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Saves every incoming update to a file in JSON Lines format
+/// and passes it to the inner handler.
+///
+/// The file can be processed later using [`crate::replay::ReplayRunner`].
+///
+/// A failure to save an update is logged and does not prevent the update from being handled.
+#[derive(Clone)]
+pub struct UpdateRecorder<H> {
+    handler: H,
+    path: PathBuf,
+    clock: Clock,
+    lock: Arc<Mutex<()>>,
+}
+
+impl<H> UpdateRecorder<H> {
+    /// Creates a new `UpdateRecorder`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - A path to the file; it will be created if it doesn't exist.
+    /// * `handler` - A handler to pass updates to, e.g. [`crate::App`].
+    pub fn new<P>(path: P, handler: H) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self {
+            handler,
+            path: path.into(),
+            clock: Clock::system(),
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Sets a clock to get the time when an update has been received.
+    ///
+    /// The system clock is used by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `clock` - The clock to use.
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    async fn record(&self, update: &Update) -> std::io::Result<()> {
+        let record = RecordedUpdate::new(update.clone(), self.clock.now());
+        let mut data = serde_json::to_vec(&record)?;
+        data.push(b'\n');
+        let _lock = self.lock.lock().await;
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path).await?;
+        file.write_all(&data).await?;
+        file.flush().await
+    }
+}
+
+impl<H> UpdateHandler for UpdateRecorder<H>
+where
+    H: UpdateHandler + Sync,
+{
+    async fn handle(&self, update: Update) {
+        if let Err(err) = self.record(&update).await {
+            log::error!("Failed to record update {}: {err}", update.id);
+        }
+        self.handler.handle(update).await
+    }
+}
@@ -0,0 +1,67 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, UNIX_EPOCH},
+};
+
+use super::*;
+
+#[derive(Clone, Default)]
+struct Collector(Arc<Mutex<Vec<i64>>>);
+
+impl UpdateHandler for Collector {
+    async fn handle(&self, update: Update) {
+        self.0.lock().unwrap().push(update.id);
+    }
+}
+
+fn create_update(update_id: i64) -> Update {
+    serde_json::from_value(serde_json::json!({
+        "update_id": update_id,
+        "message": {
+            "message_id": 1111,
+            "date": 0,
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "chat": {"id": 1, "type": "private", "first_name": "test"},
+            "text": "test"
+        }
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn recorder() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let path = tmpdir.path().join("updates.jsonl");
+    let collector = Collector::default();
+    let clock = Clock::virtual_time(UNIX_EPOCH + Duration::from_secs(1));
+    let recorder = UpdateRecorder::new(&path, collector.clone()).with_clock(clock.clone());
+    recorder.handle(create_update(1)).await;
+    clock.set(UNIX_EPOCH + Duration::from_secs(2));
+    recorder.handle(create_update(2)).await;
+    assert_eq!(*collector.0.lock().unwrap(), vec![1, 2]);
+
+    let records = tokio::fs::read_to_string(&path)
+        .await
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect::<Vec<RecordedUpdate>>();
+    assert_eq!(
+        records,
+        vec![
+            RecordedUpdate::new(create_update(1), UNIX_EPOCH + Duration::from_secs(1)),
+            RecordedUpdate::new(create_update(2), UNIX_EPOCH + Duration::from_secs(2)),
+        ]
+    );
+    assert_eq!(records[0].received_at, 1000);
+}
+
+#[tokio::test]
+async fn recorder_failed() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let collector = Collector::default();
+    // a directory can not be opened for writing
+    let recorder = UpdateRecorder::new(tmpdir.path(), collector.clone());
+    recorder.handle(create_update(1)).await;
+    assert_eq!(*collector.0.lock().unwrap(), vec![1]);
+}
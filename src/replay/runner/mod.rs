@@ -0,0 +1,112 @@
+use std::{error::Error, fmt, io::Error as IoError, path::PathBuf, time::UNIX_EPOCH};
+
+use serde_json::Error as JsonError;
+use tokio::fs::read_to_string;
+
+use crate::{
+    handler::UpdateHandler,
+    replay::{clock::Clock, record::RecordedUpdate},
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Feeds updates saved by [`crate::replay::UpdateRecorder`] to a handler.
+///
+/// Updates are handled one by one in the recorded order without any delays.
+/// Before an update is handled, the time of a [virtual clock](Self::get_clock)
+/// is set to the time when the update has been received.
+///
+/// Use [`crate::replay::MockApi`] to create a client which does not require a bot token.
+pub struct ReplayRunner {
+    path: PathBuf,
+    clock: Clock,
+}
+
+impl ReplayRunner {
+    /// Creates a new `ReplayRunner`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - A path to the recorded file.
+    pub fn new<P>(path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self {
+            path: path.into(),
+            clock: Clock::virtual_time(UNIX_EPOCH),
+        }
+    }
+
+    /// Returns the virtual clock.
+    ///
+    /// Insert the clock into the [`crate::Context`] of the app,
+    /// so handlers can obtain the time of an update being replayed.
+    pub fn get_clock(&self) -> Clock {
+        self.clock.clone()
+    }
+
+    /// Reads the recorded file and passes updates to the handler.
+    ///
+    /// Returns the number of replayed updates.
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - The handler to pass updates to, e.g. [`crate::App`].
+    pub async fn run<H>(&self, handler: &H) -> Result<usize, ReplayError>
+    where
+        H: UpdateHandler,
+    {
+        let data = read_to_string(&self.path).await.map_err(ReplayError::Io)?;
+        let records = data
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(idx, line)| {
+                serde_json::from_str::<RecordedUpdate>(line).map_err(|err| ReplayError::Json { line: idx + 1, err })
+            })
+            .collect::<Result<Vec<RecordedUpdate>, ReplayError>>()?;
+        let count = records.len();
+        for record in records {
+            self.clock.set(record.get_received_at());
+            log::debug!("Replaying update {}", record.update.id);
+            handler.handle(record.update).await;
+        }
+        Ok(count)
+    }
+}
+
+/// An error when replaying updates.
+#[derive(Debug)]
+pub enum ReplayError {
+    /// Could not read the file.
+    Io(IoError),
+    /// Could not deserialize an update.
+    Json {
+        /// Number of the line, starting from 1.
+        line: usize,
+        /// The actual error.
+        err: JsonError,
+    },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        use self::ReplayError::*;
+        match self {
+            Io(err) => write!(out, "Replay IO error: {err}"),
+            Json { line, err } => write!(out, "Replay JSON error at line {line}: {err}"),
+        }
+    }
+}
+
+impl Error for ReplayError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        use self::ReplayError::*;
+        match self {
+            Io(err) => Some(err),
+            Json { err, .. } => Some(err),
+        }
+    }
+}
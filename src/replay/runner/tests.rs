@@ -0,0 +1,103 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    api::Client,
+    core::{App, Context, Ref},
+    handler::UpdateHandler,
+    replay::{mock::MockApi, recorder::UpdateRecorder},
+    types::{ChatPeerId, SendMessage, Text, Update},
+};
+
+use super::*;
+
+struct Noop;
+
+impl UpdateHandler for Noop {
+    async fn handle(&self, _update: Update) {}
+}
+
+#[derive(Clone, Default)]
+struct Echoed {
+    messages: Arc<Mutex<Vec<(String, SystemTime)>>>,
+}
+
+async fn echo(client: Ref<Client>, clock: Ref<Clock>, echoed: Ref<Echoed>, chat_id: ChatPeerId, text: Text) {
+    echoed.messages.lock().unwrap().push((text.data.clone(), clock.now()));
+    client.execute(SendMessage::new(chat_id, text.data)).await.unwrap();
+}
+
+fn create_update(update_id: i64, text: &str) -> Update {
+    serde_json::from_value(serde_json::json!({
+        "update_id": update_id,
+        "message": {
+            "message_id": 1111,
+            "date": 0,
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "chat": {"id": 1, "type": "private", "first_name": "test"},
+            "text": text
+        }
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn replay() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let path = tmpdir.path().join("updates.jsonl");
+    let recording_clock = Clock::virtual_time(UNIX_EPOCH + Duration::from_secs(100));
+    let recorder = UpdateRecorder::new(&path, Noop).with_clock(recording_clock.clone());
+    recorder.handle(create_update(1, "first")).await;
+    recording_clock.set(UNIX_EPOCH + Duration::from_secs(200));
+    recorder.handle(create_update(2, "second")).await;
+
+    let server = MockApi::default()
+        .with_result(
+            "sendMessage",
+            serde_json::json!({
+                "message_id": 1,
+                "date": 0,
+                "chat": {"id": 1, "type": "private", "first_name": "test"},
+                "text": "test"
+            }),
+        )
+        .start()
+        .await
+        .unwrap();
+    let runner = ReplayRunner::new(&path);
+    let echoed = Echoed::default();
+    let mut context = Context::default();
+    context.insert(server.get_client().unwrap());
+    context.insert(runner.get_clock());
+    context.insert(echoed.clone());
+    let app = App::new(context, echo);
+    assert_eq!(runner.run(&app).await.unwrap(), 2);
+
+    assert_eq!(
+        *echoed.messages.lock().unwrap(),
+        vec![
+            (String::from("first"), UNIX_EPOCH + Duration::from_secs(100)),
+            (String::from("second"), UNIX_EPOCH + Duration::from_secs(200)),
+        ]
+    );
+    let texts = server
+        .get_requests()
+        .into_iter()
+        .map(|request| request.get_json().unwrap()["text"].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(texts, vec!["first", "second"]);
+}
+
+#[tokio::test]
+async fn replay_invalid_data() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let path = tmpdir.path().join("updates.jsonl");
+    let runner = ReplayRunner::new(&path);
+    assert!(matches!(runner.run(&Noop).await, Err(ReplayError::Io(_))));
+
+    tokio::fs::write(&path, "\nnot a json\n").await.unwrap();
+    let err = runner.run(&Noop).await.unwrap_err();
+    assert!(matches!(err, ReplayError::Json { line: 2, .. }));
+}
@@ -0,0 +1,87 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use seance::{SessionError, SessionManager, backend::SessionBackend};
+use tokio::sync::Mutex;
+
+use crate::{core::DedupStore, types::Integer};
+
+#[cfg(all(test, feature = "session-fs"))]
+mod tests;
+
+const SESSION_ID: &str = "__carapax_dedup";
+const SESSION_KEY: &str = "ids";
+
+/// A [`DedupStore`] which keeps update IDs in a session backend.
+///
+/// Allows to carry seen IDs across restarts when a persistent backend is used.
+///
+/// Keeps a bounded number of IDs in a single session, each ID is forgotten after a given lifetime.
+///
+/// Checking and inserting an ID is atomic within the store,
+/// so use a single instance for all deliveries of updates.
+pub struct SessionDedupStore<B> {
+    manager: SessionManager<B>,
+    capacity: usize,
+    lifetime: Duration,
+    lock: Mutex<()>,
+}
+
+impl<B> SessionDedupStore<B>
+where
+    B: SessionBackend,
+{
+    /// Creates a new `SessionDedupStore`.
+    ///
+    /// # Arguments
+    ///
+    /// * `manager` - A session manager.
+    /// * `capacity` - Maximum number of IDs to keep.
+    /// * `lifetime` - How long an ID is kept.
+    pub fn new(manager: SessionManager<B>, capacity: usize, lifetime: Duration) -> Self {
+        Self {
+            manager,
+            capacity,
+            lifetime,
+            lock: Mutex::new(()),
+        }
+    }
+}
+
+impl<B> DedupStore for SessionDedupStore<B>
+where
+    B: SessionBackend + Send + 'static,
+{
+    type Error = SessionError;
+
+    async fn insert(&self, update_id: Integer) -> Result<bool, Self::Error> {
+        if self.capacity == 0 {
+            return Ok(true);
+        }
+        let _lock = self.lock.lock().await;
+        let mut session = self.manager.get_session(SESSION_ID);
+        let mut ids: VecDeque<(Integer, u128)> = session.get(SESSION_KEY).await?.unwrap_or_default();
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|x| x.as_millis())
+            .unwrap_or_default();
+        let lifetime = self.lifetime.as_millis();
+        while let Some(&(_, seen_at)) = ids.front() {
+            if now.saturating_sub(seen_at) < lifetime {
+                break;
+            }
+            ids.pop_front();
+        }
+        if ids.iter().any(|&(id, _)| id == update_id) {
+            return Ok(false);
+        }
+        while ids.len() >= self.capacity {
+            ids.pop_front();
+        }
+        ids.push_back((update_id, now));
+        session.set(SESSION_KEY, &ids).await?;
+        Ok(true)
+    }
+}
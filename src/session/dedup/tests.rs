@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use tempfile::tempdir;
+
+use crate::session::backend::fs::FilesystemBackend;
+
+use super::*;
+
+fn create_store(path: &std::path::Path, capacity: usize, lifetime: Duration) -> SessionDedupStore<FilesystemBackend> {
+    SessionDedupStore::new(SessionManager::new(FilesystemBackend::new(path)), capacity, lifetime)
+}
+
+#[tokio::test]
+async fn session_store() {
+    let tmpdir = tempdir().expect("Failed to create temp directory");
+
+    let store = create_store(tmpdir.path(), 10, Duration::from_secs(60));
+    assert!(store.insert(1).await.unwrap());
+    assert!(!store.insert(1).await.unwrap());
+    assert!(store.insert(2).await.unwrap());
+
+    // IDs survive a restart
+    let store = create_store(tmpdir.path(), 10, Duration::from_secs(60));
+    assert!(!store.insert(1).await.unwrap());
+    assert!(!store.insert(2).await.unwrap());
+    assert!(store.insert(3).await.unwrap());
+}
+
+#[tokio::test]
+async fn session_store_limits() {
+    let tmpdir = tempdir().expect("Failed to create temp directory");
+
+    let store = create_store(tmpdir.path(), 2, Duration::from_secs(60));
+    assert!(store.insert(1).await.unwrap());
+    assert!(store.insert(2).await.unwrap());
+    assert!(store.insert(3).await.unwrap());
+    // the oldest ID is evicted
+    assert!(store.insert(1).await.unwrap());
+    assert!(!store.insert(3).await.unwrap());
+
+    // lifetime shorter than a second
+    let tmpdir = tempdir().expect("Failed to create temp directory");
+    let store = create_store(tmpdir.path(), 10, Duration::from_millis(100));
+    assert!(store.insert(1).await.unwrap());
+    assert!(!store.insert(1).await.unwrap());
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert!(store.insert(1).await.unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn session_store_concurrent() {
+    let tmpdir = tempdir().expect("Failed to create temp directory");
+    let store = Arc::new(create_store(tmpdir.path(), 10, Duration::from_secs(60)));
+    let tasks: Vec<_> = (0..8)
+        .map(|_| {
+            let store = store.clone();
+            tokio::spawn(async move { store.insert(1).await.unwrap() })
+        })
+        .collect();
+    let mut inserted = 0;
+    for task in tasks {
+        if task.await.unwrap() {
+            inserted += 1;
+        }
+    }
+    assert_eq!(inserted, 1);
+}
@@ -3,11 +3,14 @@ use std::{convert::Infallible, error::Error, fmt};
 use seance::backend::SessionBackend;
 pub use seance::{Session, SessionCollector, SessionCollectorHandle, SessionError, SessionManager, backend};
 
+pub use self::dedup::*;
 use crate::{
     core::{HandlerInput, TryFromInput},
     types::{ChatPeerId, UserPeerId},
 };
 
+mod dedup;
+
 impl<B> TryFromInput for Session<B>
 where
     B: SessionBackend + Send + 'static,
@@ -0,0 +1,40 @@
+use std::{
+    convert::Infallible,
+    task::{Context as TaskContext, Poll},
+};
+
+use futures_util::future::BoxFuture;
+use tower_service::Service;
+
+use crate::{
+    core::{App, Handler, IntoHandlerResult, TryFromInput},
+    handler::UpdateHandler,
+    types::Update,
+};
+
+/// Allows to use an [`App`] as a [`Service`].
+///
+/// The service is always ready, errors of the handler are logged and never returned.
+impl<H, HI, HO> Service<Update> for App<H, HI>
+where
+    H: Handler<HI, Output = HO> + Sync + 'static,
+    HI: TryFromInput + Sync + 'static,
+    HI::Error: 'static,
+    HO: IntoHandlerResult + Send + 'static,
+{
+    type Response = ();
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<(), Infallible>>;
+
+    fn poll_ready(&mut self, _: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, update: Update) -> Self::Future {
+        let app = self.clone();
+        Box::pin(async move {
+            UpdateHandler::handle(&app, update).await;
+            Ok(())
+        })
+    }
+}
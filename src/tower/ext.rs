@@ -0,0 +1,36 @@
+use tower_layer::Layer;
+
+use crate::{
+    core::{Handler, TryFromInput},
+    tower::{handler::ServiceHandler, service::HandlerService},
+};
+
+/// Provides a shortcut for wrapping a [`Handler`] by a tower [`Layer`].
+pub trait LayerExt<L, HI>: Sized {
+    /// Shortcut to wrap a [`Handler`] with a [`Layer`].
+    ///
+    /// The returned handler keeps [`Handler::allowed_updates`] and [`Handler::describe`] of the wrapped handler.
+    ///
+    /// Example: `handler.with_layer(TimeoutLayer::new(duration))`.
+    ///
+    /// # Arguments
+    ///
+    /// * `layer` - The layer to apply to a [`HandlerService`].
+    fn with_layer(self, layer: L) -> ServiceHandler<L::Service>
+    where
+        Self: Handler<HI>,
+        HI: TryFromInput,
+        L: Layer<HandlerService<Self, HI>>,
+    {
+        let allowed_updates = self.allowed_updates();
+        let description = self.describe();
+        ServiceHandler::new(layer.layer(HandlerService::new(self))).with_handler_info(allowed_updates, description)
+    }
+}
+
+impl<L, H, HI> LayerExt<L, HI> for H
+where
+    H: Handler<HI>,
+    HI: TryFromInput,
+{
+}
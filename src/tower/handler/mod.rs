@@ -0,0 +1,109 @@
+use std::{any::type_name, collections::HashSet, error::Error, fmt, future::poll_fn};
+
+use tower_service::Service;
+
+use crate::{
+    core::{ChainResult, Handler, HandlerDescription, HandlerError, HandlerInput, TryFromInput},
+    types::AllowedUpdate,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Allows to use a [`Service`] as a [`Handler`].
+///
+/// The service must respond with a [`ChainResult`], e.g. a [`crate::tower::HandlerService`] wrapped by layers.
+/// An error of the service is converted into a [`ServiceError`].
+///
+/// A handler created using [`Self::new`] allows all kinds of updates and is described by the type of the service.
+/// Use [`crate::tower::LayerExt::with_layer`] to keep kinds and a description of the wrapped handler.
+#[derive(Clone)]
+pub struct ServiceHandler<S> {
+    service: S,
+    allowed_updates: Option<HashSet<AllowedUpdate>>,
+    description: Option<HandlerDescription>,
+}
+
+impl<S> ServiceHandler<S> {
+    /// Creates a new `ServiceHandler`.
+    ///
+    /// # Arguments
+    ///
+    /// * `service` - The service to wrap.
+    pub fn new(service: S) -> Self {
+        Self {
+            service,
+            allowed_updates: None,
+            description: None,
+        }
+    }
+
+    pub(super) fn with_handler_info(
+        mut self,
+        allowed_updates: HashSet<AllowedUpdate>,
+        description: HandlerDescription,
+    ) -> Self {
+        self.allowed_updates = Some(allowed_updates);
+        self.description = Some(description);
+        self
+    }
+}
+
+impl<S> Handler<HandlerInput> for ServiceHandler<S>
+where
+    S: Service<HandlerInput, Response = ChainResult> + Clone + Send + Sync,
+    S::Error: Into<Box<dyn Error + Send + Sync>>,
+    S::Future: Send,
+{
+    type Output = ChainResult;
+
+    async fn handle(&self, input: HandlerInput) -> Self::Output {
+        let mut service = self.service.clone();
+        if let Err(err) = poll_fn(|cx| service.poll_ready(cx)).await {
+            return ChainResult::Done(Err(HandlerError::new(ServiceError(err.into()))));
+        }
+        match service.call(input).await {
+            Ok(result) => result,
+            Err(err) => ChainResult::Done(Err(HandlerError::new(ServiceError(err.into())))),
+        }
+    }
+
+    fn allowed_updates(&self) -> HashSet<AllowedUpdate> {
+        match self.allowed_updates {
+            Some(ref allowed_updates) => allowed_updates.clone(),
+            None => HandlerInput::allowed_updates(),
+        }
+    }
+
+    fn describe(&self) -> HandlerDescription {
+        match self.description {
+            Some(ref description) => description.clone(),
+            None => HandlerDescription::new(type_name::<Self>()),
+        }
+    }
+}
+
+/// An error returned by a [`Service`] wrapped by [`ServiceHandler`].
+///
+/// Usually it is an error of a layer, e.g. an elapsed timeout.
+#[derive(Debug)]
+pub struct ServiceError(Box<dyn Error + Send + Sync>);
+
+impl ServiceError {
+    /// Returns the original error.
+    pub fn into_inner(self) -> Box<dyn Error + Send + Sync> {
+        self.0
+    }
+}
+
+impl fmt::Display for ServiceError {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        write!(out, "Service error: {}", self.0)
+    }
+}
+
+impl Error for ServiceError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.0.as_ref())
+    }
+}
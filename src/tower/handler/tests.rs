@@ -0,0 +1,150 @@
+use std::{
+    collections::HashSet,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use ::tower::{ServiceBuilder, ServiceExt, buffer::BufferLayer, limit::ConcurrencyLimitLayer, timeout::TimeoutLayer};
+
+use crate::{
+    core::{App, CallbackArgs, CallbackRouter, Chain, Context, IntoHandlerResult, Ref},
+    tower::{ext::LayerExt, service::HandlerService},
+    types::{AllowedUpdate, CallbackQuery, Update},
+};
+
+use super::*;
+
+#[derive(Clone)]
+struct Delay(Duration);
+
+#[derive(Clone, Default)]
+struct Counter(Arc<AtomicUsize>);
+
+async fn handler(Ref(Delay(delay)): Ref<Delay>, Ref(Counter(counter)): Ref<Counter>) {
+    tokio::time::sleep(delay).await;
+    counter.fetch_add(1, Ordering::SeqCst);
+}
+
+fn create_update() -> Update {
+    serde_json::from_value(serde_json::json!({
+        "update_id": 1,
+        "message": {
+            "message_id": 1111,
+            "date": 0,
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "chat": {"id": 1, "type": "private", "first_name": "test"},
+            "text": "test"
+        }
+    }))
+    .unwrap()
+}
+
+fn create_context(delay: Duration) -> (Arc<Context>, Counter) {
+    let counter = Counter::default();
+    let mut context = Context::default();
+    context.insert(Delay(delay));
+    context.insert(counter.clone());
+    (Arc::new(context), counter)
+}
+
+fn create_input(context: &Arc<Context>) -> HandlerInput {
+    HandlerInput {
+        update: create_update(),
+        context: context.clone(),
+        extensions: Default::default(),
+    }
+}
+
+#[tokio::test]
+async fn layer() {
+    let handler = handler.with_layer(TimeoutLayer::new(Duration::from_millis(50)));
+
+    let (context, counter) = create_context(Duration::ZERO);
+    handler.handle(create_input(&context)).await.into_result().unwrap();
+    assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+
+    let (context, counter) = create_context(Duration::from_millis(200));
+    let err = handler.handle(create_input(&context)).await.into_result().unwrap_err();
+    assert_eq!(err.to_string(), "Service error: request timed out");
+    assert_eq!(counter.0.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn chain() {
+    let service = ServiceBuilder::new()
+        .layer(ConcurrencyLimitLayer::new(1))
+        .service(HandlerService::new(Chain::all().with(handler).with(handler)));
+    let chain = Chain::once().with(ServiceHandler::new(service));
+    let (context, counter) = create_context(Duration::ZERO);
+    chain.handle(create_input(&context)).await.into_result().unwrap();
+    assert_eq!(counter.0.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn app() {
+    let (context, counter) = create_context(Duration::ZERO);
+    let context = Arc::into_inner(context).unwrap();
+    let app = App::new(context, handler);
+    app.clone().oneshot(create_update()).await.unwrap();
+    app.oneshot(create_update()).await.unwrap();
+    assert_eq!(counter.0.load(Ordering::SeqCst), 2);
+}
+
+async fn callback_handler(Ref(Counter(counter)): Ref<Counter>, _: CallbackQuery) {
+    counter.fetch_add(10, Ordering::SeqCst);
+}
+
+#[tokio::test]
+async fn skipped() {
+    let layered = callback_handler.with_layer(TimeoutLayer::new(Duration::from_millis(50)));
+    let (context, _) = create_context(Duration::ZERO);
+    assert!(matches!(
+        layered.handle(create_input(&context)).await,
+        ChainResult::Skipped
+    ));
+
+    let chain = Chain::once().with(layered).with(handler);
+    let (context, counter) = create_context(Duration::ZERO);
+    chain.handle(create_input(&context)).await.into_result().unwrap();
+    assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn handler_info() {
+    let layered = callback_handler.with_layer(TimeoutLayer::new(Duration::from_millis(50)));
+    assert_eq!(
+        Handler::allowed_updates(&layered),
+        HashSet::from([AllowedUpdate::CallbackQuery])
+    );
+    assert_eq!(layered.describe(), callback_handler.describe());
+}
+
+async fn args_handler(Ref(Counter(counter)): Ref<Counter>, args: CallbackArgs) {
+    counter.fetch_add(args.parse(0).unwrap(), Ordering::SeqCst);
+}
+
+#[tokio::test]
+async fn buffer() {
+    let router = CallbackRouter::new().with_route("add:*", args_handler.with_layer(BufferLayer::new(1)));
+    let (context, counter) = create_context(Duration::ZERO);
+    let update = serde_json::from_value(serde_json::json!({
+        "update_id": 1,
+        "callback_query": {
+            "id": "query-id",
+            "from": {"id": 1, "is_bot": false, "first_name": "test"},
+            "chat_instance": "instance",
+            "data": "add:5"
+        }
+    }))
+    .unwrap();
+    let input = HandlerInput {
+        update,
+        context,
+        extensions: Default::default(),
+    };
+    router.handle(input).await.into_result().unwrap();
+    assert_eq!(counter.0.load(Ordering::SeqCst), 5);
+}
@@ -0,0 +1,6 @@
+pub use self::{ext::*, handler::*, service::*};
+
+mod app;
+mod ext;
+mod handler;
+mod service;
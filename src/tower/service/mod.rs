@@ -0,0 +1,77 @@
+use std::{
+    convert::Infallible,
+    marker::PhantomData,
+    task::{Context as TaskContext, Poll},
+};
+
+use futures_util::future::BoxFuture;
+use tower_service::Service;
+
+use crate::core::{ChainResult, Handler, HandlerError, HandlerInput, TryFromInput};
+
+/// Allows to use a [`Handler`] as a [`Service`].
+///
+/// The service responds with the result of the handler and never fails,
+/// so errors of tower layers can be distinguished from errors of the handler.
+/// When the input can not be converted into the input of the handler,
+/// the service responds with [`ChainResult::Skipped`].
+///
+/// Use it together with [`crate::tower::ServiceHandler`] in order to add the service
+/// to a [`crate::Chain`] or an [`crate::App`], see also [`crate::tower::LayerExt`].
+pub struct HandlerService<H, HI> {
+    handler: H,
+    handler_input: PhantomData<HI>,
+}
+
+impl<H, HI> HandlerService<H, HI> {
+    /// Creates a new `HandlerService`.
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - The handler to wrap, e.g. a [`crate::Chain`].
+    pub fn new(handler: H) -> Self {
+        Self {
+            handler,
+            handler_input: PhantomData,
+        }
+    }
+}
+
+impl<H, HI> Clone for HandlerService<H, HI>
+where
+    H: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            handler: self.handler.clone(),
+            handler_input: PhantomData,
+        }
+    }
+}
+
+impl<H, HI> Service<HandlerInput> for HandlerService<H, HI>
+where
+    H: Handler<HI> + 'static,
+    H::Output: Into<ChainResult>,
+    HI: TryFromInput + 'static,
+    HI::Error: 'static,
+{
+    type Response = ChainResult;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<ChainResult, Infallible>>;
+
+    fn poll_ready(&mut self, _: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, input: HandlerInput) -> Self::Future {
+        let handler = self.handler.clone();
+        Box::pin(async move {
+            Ok(match HI::try_from_input(input).await {
+                Ok(Some(input)) => handler.handle(input).await.into(),
+                Ok(None) => ChainResult::Skipped,
+                Err(err) => ChainResult::Err(HandlerError::convert(err)),
+            })
+        })
+    }
+}